@@ -2,7 +2,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let base_path = "spark/connector/connect/common/src/main/protobuf";
     let proto_files = vec![
         "base.proto",
+        "catalog.proto",
         "commands.proto",
+        "common.proto",
         "expressions.proto",
         "relations.proto",
         "types.proto",
@@ -1,5 +1,4 @@
 use arrow::util::pretty;
-use spark_connect_rust;
 use spark_connect_rust::RemoteSparkSession;
 
 #[tokio::main]
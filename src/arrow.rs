@@ -1,9 +1,11 @@
 use crate::error::DeserializationError;
+use crate::error::InvalidArgumentError;
 use crate::error::SparkError;
 use crate::spark::execute_plan_response::ArrowBatch;
 use arrow::error::ArrowError;
 use arrow::record_batch::RecordBatch;
 use arrow_ipc::reader::StreamReader;
+use arrow_ipc::writer::StreamWriter;
 use std::io::Read;
 
 pub struct ArrowBatchReader {
@@ -26,6 +28,24 @@ pub fn deserialize(batch: ArrowBatch) -> Result<Vec<RecordBatch>, SparkError> {
     Ok(rows)
 }
 
+/// Serializes the batches, which must share the same schema, as an Arrow
+/// IPC stream.
+pub fn serialize(batches: &[RecordBatch]) -> Result<Vec<u8>, SparkError> {
+    let schema = match batches.first() {
+        Some(batch) => batch.schema(),
+        None => {
+            return Err(SparkError::InvalidArgument(InvalidArgumentError(
+                "At least one record batch is required".to_string(),
+            )))
+        }
+    };
+    let mut writer = StreamWriter::try_new(Vec::new(), &schema)?;
+    for batch in batches {
+        writer.write(batch)?;
+    }
+    Ok(writer.into_inner()?)
+}
+
 impl From<ArrowError> for SparkError {
     fn from(err: ArrowError) -> Self {
         SparkError::DeserializationFailed(DeserializationError(err.to_string()))
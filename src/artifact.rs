@@ -0,0 +1,209 @@
+use crate::error::{InvalidArgumentError, SparkError};
+use crate::spark::add_artifacts_request::{
+    ArtifactChunk, Batch, BeginChunkedArtifact, Payload, SingleChunkArtifact,
+};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Size of the chunks artifacts are split into, coherent with Python Spark Connect.
+const CHUNK_SIZE: usize = 32 * 1024;
+
+const CACHE_PREFIX: &str = "cache";
+pub(crate) const JAR_PREFIX: &str = "jars";
+pub(crate) const CLASS_PREFIX: &str = "classes";
+pub(crate) const FILE_PREFIX: &str = "files";
+pub(crate) const ARCHIVE_PREFIX: &str = "archives";
+
+/// In-memory artifact to be uploaded to the server through `AddArtifacts`.
+#[derive(Debug, Clone)]
+pub(crate) struct Artifact {
+    pub(crate) name: String,
+    pub(crate) data: Vec<u8>,
+}
+
+impl Artifact {
+    /// Cache artifacts are named after the SHA-256 of their content, so that
+    /// the same data is stored only once per session.
+    pub(crate) fn cache(data: Vec<u8>) -> (String, Artifact) {
+        let hash = hex::encode(Sha256::digest(&data));
+        let artifact = Artifact {
+            name: cache_artifact_name(&hash),
+            data,
+        };
+        (hash, artifact)
+    }
+
+    /// Reads a local file into an artifact named `<prefix>/<file name>`.
+    pub(crate) fn from_path(prefix: &str, path: &str) -> Result<Artifact, SparkError> {
+        let file_name = Path::new(path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                SparkError::InvalidArgument(InvalidArgumentError(format!(
+                    "Invalid artifact path {}",
+                    path
+                )))
+            })?;
+        let data = std::fs::read(path).map_err(|err| {
+            SparkError::InvalidArgument(InvalidArgumentError(format!(
+                "Cannot read artifact {}: {}",
+                path, err
+            )))
+        })?;
+        Ok(Artifact {
+            name: format!("{}/{}", prefix, file_name),
+            data,
+        })
+    }
+
+    /// Reads a local file, choosing the kind of artifact from its extension:
+    /// only `.jar` and `.class` files are supported.
+    pub(crate) fn from_extension(path: &str) -> Result<Artifact, SparkError> {
+        match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("jar") => Artifact::from_path(JAR_PREFIX, path),
+            Some("class") => Artifact::from_path(CLASS_PREFIX, path),
+            _ => Err(SparkError::InvalidArgument(InvalidArgumentError(format!(
+                "Unsupported artifact {}, only .jar and .class files can be added",
+                path
+            )))),
+        }
+    }
+
+    /// Splits the artifact into a `BeginChunkedArtifact` followed by the
+    /// remaining chunks, each carrying its own CRC.
+    pub(crate) fn into_payloads(self) -> Vec<Payload> {
+        let mut chunks = self.data.chunks(CHUNK_SIZE).map(chunk);
+        let initial_chunk = chunks.next().unwrap_or_else(|| chunk(&[]));
+        let num_chunks = self.data.chunks(CHUNK_SIZE).count().max(1);
+        let begin = Payload::BeginChunk(BeginChunkedArtifact {
+            name: self.name,
+            total_bytes: self.data.len() as i64,
+            num_chunks: num_chunks as i64,
+            initial_chunk: Some(initial_chunk),
+        });
+        std::iter::once(begin)
+            .chain(chunks.map(Payload::Chunk))
+            .collect()
+    }
+}
+
+/// Builds the `AddArtifacts` payloads: artifacts fitting in a single chunk
+/// are grouped into batches of at most `CHUNK_SIZE` bytes, larger ones are
+/// sent in chunks.
+pub(crate) fn payloads(artifacts: Vec<Artifact>) -> Vec<Payload> {
+    let mut payloads = vec![];
+    let mut batch: Vec<SingleChunkArtifact> = vec![];
+    let mut batch_size = 0;
+    for artifact in artifacts {
+        if artifact.data.len() > CHUNK_SIZE {
+            payloads.extend(artifact.into_payloads());
+            continue;
+        }
+        if batch_size + artifact.data.len() > CHUNK_SIZE {
+            payloads.push(Payload::Batch(Batch {
+                artifacts: std::mem::take(&mut batch),
+            }));
+            batch_size = 0;
+        }
+        batch_size += artifact.data.len();
+        batch.push(SingleChunkArtifact {
+            data: Some(chunk(&artifact.data)),
+            name: artifact.name,
+        });
+    }
+    if !batch.is_empty() {
+        payloads.push(Payload::Batch(Batch { artifacts: batch }));
+    }
+    payloads
+}
+
+pub(crate) fn cache_artifact_name(hash: &str) -> String {
+    format!("{}/{}", CACHE_PREFIX, hash)
+}
+
+fn chunk(data: &[u8]) -> ArtifactChunk {
+    ArtifactChunk {
+        data: data.to_vec(),
+        crc: crc32fast::hash(data) as i64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_artifact_is_named_after_content_hash() {
+        let (hash, artifact) = Artifact::cache(b"spark".to_vec());
+        assert_eq!(
+            hash,
+            "92f473809e5979a7da2b7f52771b3a9e3d7105dcb0f24ae333c5bf279b863d73"
+        );
+        assert_eq!(artifact.name, format!("cache/{}", hash));
+    }
+
+    #[test]
+    fn test_into_payloads_splits_in_chunks_with_crc() {
+        let data = vec![7u8; CHUNK_SIZE * 2 + 1];
+        let payloads = Artifact {
+            name: "cache/abc".to_string(),
+            data: data.clone(),
+        }
+        .into_payloads();
+        assert_eq!(payloads.len(), 3);
+        match &payloads[0] {
+            Payload::BeginChunk(begin) => {
+                assert_eq!(begin.name, "cache/abc");
+                assert_eq!(begin.total_bytes, data.len() as i64);
+                assert_eq!(begin.num_chunks, 3);
+                let initial = begin.initial_chunk.as_ref().unwrap();
+                assert_eq!(initial.data.len(), CHUNK_SIZE);
+                assert_eq!(initial.crc, crc32fast::hash(&data[..CHUNK_SIZE]) as i64);
+            }
+            other => panic!("Unexpected payload {:?}", other),
+        }
+        match &payloads[2] {
+            Payload::Chunk(chunk) => assert_eq!(chunk.data, vec![7u8]),
+            other => panic!("Unexpected payload {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_payloads_batch_small_artifacts() {
+        let artifact = |name: &str, size: usize| Artifact {
+            name: name.to_string(),
+            data: vec![0u8; size],
+        };
+        let payloads = payloads(vec![
+            artifact("files/a", 10),
+            artifact("files/b", CHUNK_SIZE - 10),
+            artifact("files/c", CHUNK_SIZE + 1),
+            artifact("files/d", 1),
+        ]);
+        let kinds: Vec<_> = payloads
+            .iter()
+            .map(|payload| match payload {
+                Payload::Batch(batch) => batch
+                    .artifacts
+                    .iter()
+                    .map(|artifact| artifact.name.clone())
+                    .collect::<Vec<_>>()
+                    .join(","),
+                Payload::BeginChunk(begin) => format!("begin {}", begin.name),
+                Payload::Chunk(_) => "chunk".to_string(),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec!["begin files/c", "chunk", "files/a,files/b", "files/d"]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_extension_is_rejected() {
+        assert!(matches!(
+            Artifact::from_extension("/tmp/data.csv"),
+            Err(SparkError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,322 @@
+use crate::dataframe::DataFrame;
+use crate::de;
+use crate::error::SparkError;
+use crate::session::RemoteSparkSession;
+use crate::spark;
+use crate::spark::catalog::CatType;
+use arrow::record_batch::RecordBatch;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Database {
+    pub name: String,
+    pub catalog: Option<String>,
+    pub description: Option<String>,
+    pub location_uri: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    pub name: String,
+    pub catalog: Option<String>,
+    pub namespace: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub table_type: String,
+    pub is_temporary: bool,
+}
+
+/// Column of a table, as listed by `Catalog::list_columns`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Column {
+    pub name: String,
+    pub description: Option<String>,
+    pub data_type: String,
+    pub nullable: bool,
+    pub is_partition: bool,
+    pub is_bucket: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Function {
+    pub name: String,
+    pub catalog: Option<String>,
+    pub namespace: Option<Vec<String>>,
+    pub description: Option<String>,
+    pub class_name: String,
+    pub is_temporary: bool,
+}
+
+/// Access to the databases, tables and functions of the session catalog,
+/// see `RemoteSparkSession::catalog`.
+pub struct Catalog {
+    session: Rc<RemoteSparkSession>,
+}
+
+impl Catalog {
+    pub(crate) fn new(session: Rc<RemoteSparkSession>) -> Self {
+        Catalog { session }
+    }
+
+    pub async fn current_database(&self) -> Result<String, SparkError> {
+        self.fetch_scalar(CatType::CurrentDatabase(spark::CurrentDatabase {}))
+            .await
+    }
+
+    pub async fn set_current_database(&self, db_name: String) -> Result<(), SparkError> {
+        self.execute(CatType::SetCurrentDatabase(spark::SetCurrentDatabase {
+            db_name,
+        }))
+        .await
+    }
+
+    /// Lists the databases whose name matches `pattern`, all of them when
+    /// `None`.
+    pub async fn list_databases(
+        &self,
+        pattern: Option<String>,
+    ) -> Result<Vec<Database>, SparkError> {
+        self.fetch_as(CatType::ListDatabases(spark::ListDatabases { pattern }))
+            .await
+    }
+
+    /// Lists the tables and views of `db_name`, or of the current database
+    /// when `None`, including temporary views.
+    pub async fn list_tables(
+        &self,
+        db_name: Option<String>,
+        pattern: Option<String>,
+    ) -> Result<Vec<Table>, SparkError> {
+        self.fetch_as(CatType::ListTables(spark::ListTables { db_name, pattern }))
+            .await
+    }
+
+    pub async fn list_columns(
+        &self,
+        table_name: String,
+        db_name: Option<String>,
+    ) -> Result<Vec<Column>, SparkError> {
+        self.fetch_as(CatType::ListColumns(spark::ListColumns {
+            table_name,
+            db_name,
+        }))
+        .await
+    }
+
+    /// Lists the functions of `db_name`, or of the current database when
+    /// `None`, including built-in and temporary functions.
+    pub async fn list_functions(
+        &self,
+        db_name: Option<String>,
+        pattern: Option<String>,
+    ) -> Result<Vec<Function>, SparkError> {
+        self.fetch_as(CatType::ListFunctions(spark::ListFunctions {
+            db_name,
+            pattern,
+        }))
+        .await
+    }
+
+    pub async fn table_exists(
+        &self,
+        table_name: String,
+        db_name: Option<String>,
+    ) -> Result<bool, SparkError> {
+        self.fetch_scalar(CatType::TableExists(spark::TableExists {
+            table_name,
+            db_name,
+        }))
+        .await
+    }
+
+    pub async fn function_exists(
+        &self,
+        function_name: String,
+        db_name: Option<String>,
+    ) -> Result<bool, SparkError> {
+        self.fetch_scalar(CatType::FunctionExists(spark::FunctionExists {
+            function_name,
+            db_name,
+        }))
+        .await
+    }
+
+    /// Creates a table from the `source` data source, `parquet` by default,
+    /// and returns it as a `DataFrame`. The table is managed unless `path`
+    /// is set.
+    pub async fn create_table(
+        &self,
+        table_name: String,
+        path: Option<String>,
+        source: Option<String>,
+        description: Option<String>,
+        schema: Option<spark::DataType>,
+        options: HashMap<String, String>,
+    ) -> Result<DataFrame, SparkError> {
+        self.execute(CatType::CreateTable(spark::CreateTable {
+            table_name: table_name.clone(),
+            path,
+            source,
+            description,
+            schema,
+            options,
+        }))
+        .await?;
+        Ok(self.session.clone().table(table_name))
+    }
+
+    /// Creates a table over the data at `path` and returns it as a
+    /// `DataFrame`.
+    pub async fn create_external_table(
+        &self,
+        table_name: String,
+        path: String,
+        source: Option<String>,
+        schema: Option<spark::DataType>,
+        options: HashMap<String, String>,
+    ) -> Result<DataFrame, SparkError> {
+        self.execute(CatType::CreateExternalTable(spark::CreateExternalTable {
+            table_name: table_name.clone(),
+            path: Some(path),
+            source,
+            schema,
+            options,
+        }))
+        .await?;
+        Ok(self.session.clone().table(table_name))
+    }
+
+    /// Returns whether the view existed and was dropped.
+    pub async fn drop_temp_view(&self, view_name: String) -> Result<bool, SparkError> {
+        self.fetch_scalar(CatType::DropTempView(spark::DropTempView { view_name }))
+            .await
+    }
+
+    /// Caches the table, with the default storage level of Spark when
+    /// `storage_level` is `None`.
+    pub async fn cache_table(
+        &self,
+        table_name: String,
+        storage_level: Option<spark::StorageLevel>,
+    ) -> Result<(), SparkError> {
+        self.execute(CatType::CacheTable(spark::CacheTable {
+            table_name,
+            storage_level,
+        }))
+        .await
+    }
+
+    pub async fn uncache_table(&self, table_name: String) -> Result<(), SparkError> {
+        self.execute(CatType::UncacheTable(spark::UncacheTable { table_name }))
+            .await
+    }
+
+    pub async fn is_cached(&self, table_name: String) -> Result<bool, SparkError> {
+        self.fetch_scalar(CatType::IsCached(spark::IsCached { table_name }))
+            .await
+    }
+
+    /// Invalidates the cached data and metadata of the table.
+    pub async fn refresh_table(&self, table_name: String) -> Result<(), SparkError> {
+        self.execute(CatType::RefreshTable(spark::RefreshTable { table_name }))
+            .await
+    }
+
+    /// Adds the partitions found in the directory of a partitioned table to
+    /// the catalog.
+    pub async fn recover_partitions(&self, table_name: String) -> Result<(), SparkError> {
+        self.execute(CatType::RecoverPartitions(spark::RecoverPartitions {
+            table_name,
+        }))
+        .await
+    }
+
+    pub async fn clear_cache(&self) -> Result<(), SparkError> {
+        self.execute(CatType::ClearCache(spark::ClearCache {}))
+            .await
+    }
+
+    async fn fetch(&self, cat_type: CatType) -> Result<Vec<RecordBatch>, SparkError> {
+        self.session.fetch(relation(cat_type)).await
+    }
+
+    async fn execute(&self, cat_type: CatType) -> Result<(), SparkError> {
+        self.fetch(cat_type).await?;
+        Ok(())
+    }
+
+    async fn fetch_as<T: DeserializeOwned>(&self, cat_type: CatType) -> Result<Vec<T>, SparkError> {
+        de::from_record_batches(&self.fetch(cat_type).await?)
+    }
+
+    /// Operations returning a single value answer with a one row, one column
+    /// result.
+    async fn fetch_scalar<T: DeserializeOwned>(&self, cat_type: CatType) -> Result<T, SparkError> {
+        self.session.fetch_scalar(relation(cat_type)).await
+    }
+}
+
+fn relation(cat_type: CatType) -> spark::Relation {
+    spark::Relation {
+        common: None,
+        rel_type: Some(spark::relation::RelType::Catalog(spark::Catalog {
+            cat_type: Some(cat_type),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, BooleanArray, ListBuilder, StringArray, StringBuilder};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_tables_deserialize_from_catalog_result() {
+        let mut namespace = ListBuilder::new(StringBuilder::new());
+        namespace.values().append_value("default");
+        namespace.append(true);
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["employees"])) as ArrayRef,
+            ),
+            (
+                "catalog",
+                Arc::new(StringArray::from(vec![Some("spark_catalog")])) as ArrayRef,
+            ),
+            ("namespace", Arc::new(namespace.finish()) as ArrayRef),
+            (
+                "description",
+                Arc::new(StringArray::from(vec![None::<&str>])) as ArrayRef,
+            ),
+            (
+                "tableType",
+                Arc::new(StringArray::from(vec!["EXTERNAL"])) as ArrayRef,
+            ),
+            (
+                "isTemporary",
+                Arc::new(BooleanArray::from(vec![false])) as ArrayRef,
+            ),
+        ])
+        .unwrap();
+        let tables: Vec<Table> = de::from_record_batch(&batch).unwrap();
+        assert_eq!(
+            tables,
+            vec![Table {
+                name: "employees".to_string(),
+                catalog: Some("spark_catalog".to_string()),
+                namespace: Some(vec!["default".to_string()]),
+                description: None,
+                table_type: "EXTERNAL".to_string(),
+                is_temporary: false,
+            }]
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::spark;
+use crate::spark::expression::sort_order::{NullOrdering, SortDirection};
+use crate::spark::expression::ExprType;
+use crate::window::WindowSpec;
+
+/// Expression over the columns of a `DataFrame`, to be used in `select` or
+/// `filter`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub(crate) expression: spark::Expression,
+}
+
+impl Column {
+    pub fn alias(self, name: &str) -> Column {
+        Column::from(ExprType::Alias(Box::new(spark::expression::Alias {
+            expr: Some(Box::new(self.expression)),
+            name: vec![name.to_string()],
+            metadata: None,
+        })))
+    }
+
+    /// Ascending sort order, with nulls first.
+    pub fn asc(self) -> Column {
+        self.sort_order(SortDirection::Ascending, NullOrdering::SortNullsFirst)
+    }
+
+    pub fn asc_nulls_last(self) -> Column {
+        self.sort_order(SortDirection::Ascending, NullOrdering::SortNullsLast)
+    }
+
+    /// Descending sort order, with nulls last.
+    pub fn desc(self) -> Column {
+        self.sort_order(SortDirection::Descending, NullOrdering::SortNullsLast)
+    }
+
+    pub fn desc_nulls_first(self) -> Column {
+        self.sort_order(SortDirection::Descending, NullOrdering::SortNullsFirst)
+    }
+
+    fn sort_order(self, direction: SortDirection, null_ordering: NullOrdering) -> Column {
+        Column::from(ExprType::SortOrder(Box::new(
+            self.sort_order_expression(direction, null_ordering),
+        )))
+    }
+
+    fn sort_order_expression(
+        self,
+        direction: SortDirection,
+        null_ordering: NullOrdering,
+    ) -> spark::expression::SortOrder {
+        spark::expression::SortOrder {
+            child: Some(Box::new(self.expression)),
+            direction: direction.into(),
+            null_ordering: null_ordering.into(),
+        }
+    }
+
+    /// Evaluates the window or aggregate function over `window`, e.g.
+    /// `functions::rank().over(&window)`.
+    pub fn over(self, window: &WindowSpec) -> Column {
+        window.apply(self)
+    }
+
+    /// Keeps sort orders as they are, and sorts other columns in ascending
+    /// order.
+    pub(crate) fn into_sort_order(self) -> spark::expression::SortOrder {
+        match self.expression.expr_type {
+            Some(ExprType::SortOrder(sort_order)) => *sort_order,
+            _ => self.sort_order_expression(SortDirection::Ascending, NullOrdering::SortNullsFirst),
+        }
+    }
+}
+
+/// Refers to the column `name`, which may be qualified, e.g. `employees.name`.
+pub fn col(name: &str) -> Column {
+    Column::from(ExprType::UnresolvedAttribute(
+        spark::expression::UnresolvedAttribute {
+            unparsed_identifier: name.to_string(),
+            plan_id: None,
+        },
+    ))
+}
+
+/// Parses `sql` as a SQL expression, e.g. `salary * 2 AS double_salary`.
+pub fn expr(sql: &str) -> Column {
+    Column::from(ExprType::ExpressionString(
+        spark::expression::ExpressionString {
+            expression: sql.to_string(),
+        },
+    ))
+}
+
+impl From<ExprType> for Column {
+    fn from(expr_type: ExprType) -> Self {
+        Column {
+            expression: spark::Expression {
+                expr_type: Some(expr_type),
+            },
+        }
+    }
+}
+
+impl From<spark::expression::Literal> for Column {
+    fn from(literal: spark::expression::Literal) -> Self {
+        Column::from(ExprType::Literal(literal))
+    }
+}
+
+impl From<spark::Expression> for Column {
+    fn from(expression: spark::Expression) -> Self {
+        Column { expression }
+    }
+}
+
+impl From<Column> for spark::Expression {
+    fn from(column: Column) -> Self {
+        column.expression
+    }
+}
+
+impl From<&str> for Column {
+    fn from(name: &str) -> Self {
+        col(name)
+    }
+}
+
+impl From<String> for Column {
+    fn from(name: String) -> Self {
+        col(&name)
+    }
+}
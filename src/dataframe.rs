@@ -1,13 +1,27 @@
-use crate::error::SparkError;
+use crate::column::Column;
+use crate::de;
+use crate::error::{InvalidArgumentError, SparkError};
+use crate::metrics::{Observation, QueryResult};
+use crate::options::JdbcPartitioning;
 use crate::plan;
 use crate::session;
 use crate::spark;
+use crate::spark::expression::literal::LiteralType;
+use crate::spark::expression::Literal;
+use crate::spark::join::JoinType;
 use crate::spark::write_operation;
+use crate::spark::write_operation::save_table::TableSaveMethod;
+use crate::spark::write_operation_v2;
+use crate::types;
 use arrow::record_batch::RecordBatch;
+use futures::future;
+use futures::stream::{self, Stream, TryStreamExt};
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
 
 use spark::expression::ExprType;
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 pub struct DataFrame {
     pub(crate) session: Rc<session::RemoteSparkSession>,
     pub(crate) plan: Box<dyn plan::Plan>,
@@ -30,13 +44,24 @@ impl DataFrameReader {
         }
     }
 
-    pub fn schema(mut self, schema: Option<String>) -> Self {
-        self.schema = schema;
+    /// Sets the schema of the data, given as DDL, e.g. `name STRING, salary
+    /// BIGINT`, or as a `spark::DataType`, instead of letting Spark infer it.
+    /// `None` clears a schema set earlier. Fails when a `spark::DataType`
+    /// can't be formatted as DDL, e.g. when it is missing its kind.
+    pub fn schema<S: ReaderSchema>(mut self, schema: S) -> Result<Self, SparkError> {
+        self.schema = schema.into_ddl()?;
+        Ok(self)
+    }
+
+    /// Adds the options, given as a map or as one of the typed options of
+    /// the `options` module, to the ones already set.
+    pub fn options<O: Into<HashMap<String, String>>>(mut self, options: O) -> Self {
+        self.options.extend(options.into());
         self
     }
 
-    pub fn options(mut self, options: HashMap<String, String>) -> Self {
-        self.options = options;
+    pub fn option(mut self, key: String, value: String) -> Self {
+        self.options.insert(key, value);
         self
     }
 
@@ -45,6 +70,27 @@ impl DataFrameReader {
         self
     }
 
+    pub fn csv(self, paths: Vec<String>) -> DataFrame {
+        self.format("csv".to_string()).load(paths)
+    }
+
+    pub fn json(self, paths: Vec<String>) -> DataFrame {
+        self.format("json".to_string()).load(paths)
+    }
+
+    pub fn parquet(self, paths: Vec<String>) -> DataFrame {
+        self.format("parquet".to_string()).load(paths)
+    }
+
+    pub fn orc(self, paths: Vec<String>) -> DataFrame {
+        self.format("orc".to_string()).load(paths)
+    }
+
+    /// Reads text files into a single `value` column, one row per line.
+    pub fn text(self, paths: Vec<String>) -> DataFrame {
+        self.format("text".to_string()).load(paths)
+    }
+
     pub fn table(self, table_name: String) -> DataFrame {
         DataFrame {
             session: self.session,
@@ -56,6 +102,87 @@ impl DataFrameReader {
     }
 
     pub fn load(self, paths: Vec<String>) -> DataFrame {
+        self.load_with_predicates(paths, vec![])
+    }
+
+    /// Reads the table `table` of the database at `url`, as a single
+    /// partition. `properties` are the JDBC connection properties, such as
+    /// `user`, `password` or `driver`.
+    pub fn jdbc(
+        self,
+        url: String,
+        table: String,
+        properties: HashMap<String, String>,
+    ) -> DataFrame {
+        self.jdbc_options(url, table, properties).load(vec![])
+    }
+
+    /// Like `jdbc`, but reads in parallel, splitting the values of a numeric,
+    /// date or timestamp column into `num_partitions` ranges, see
+    /// `JdbcPartitioning`.
+    pub fn jdbc_with_partitioning(
+        self,
+        url: String,
+        table: String,
+        partitioning: JdbcPartitioning,
+        properties: HashMap<String, String>,
+    ) -> DataFrame {
+        self.jdbc_options(url, table, properties)
+            .options(partitioning)
+            .load(vec![])
+    }
+
+    /// Like `jdbc`, but reads one partition per `WHERE` clause condition in
+    /// `predicates`.
+    pub fn jdbc_with_predicates(
+        self,
+        url: String,
+        table: String,
+        predicates: Vec<String>,
+        properties: HashMap<String, String>,
+    ) -> DataFrame {
+        self.jdbc_options(url, table, properties)
+            .load_with_predicates(vec![], predicates)
+    }
+
+    /// Parses the JSON strings of the single string column of `df`, e.g.
+    /// payloads read from Kafka, into a `DataFrame`.
+    pub fn json_from(self, df: &DataFrame) -> DataFrame {
+        self.parse(spark::parse::ParseFormat::Json, df)
+    }
+
+    /// Parses the CSV lines of the single string column of `df`.
+    pub fn csv_from(self, df: &DataFrame) -> DataFrame {
+        self.parse(spark::parse::ParseFormat::Csv, df)
+    }
+
+    fn parse(self, format: spark::parse::ParseFormat, df: &DataFrame) -> DataFrame {
+        DataFrame {
+            session: self.session,
+            plan: Box::new(plan::Parse {
+                format,
+                // The server parses the DDL of unparsed types
+                schema: self.schema.map(|ddl| spark::DataType {
+                    kind: Some(spark::data_type::Kind::Unparsed(
+                        spark::data_type::Unparsed {
+                            data_type_string: ddl,
+                        },
+                    )),
+                }),
+                options: self.options,
+                input: df.plan.clone(),
+            }),
+        }
+    }
+
+    fn jdbc_options(self, url: String, table: String, properties: HashMap<String, String>) -> Self {
+        self.format("jdbc".to_string())
+            .options(properties)
+            .option("url".to_string(), url)
+            .option("dbtable".to_string(), table)
+    }
+
+    fn load_with_predicates(self, paths: Vec<String>, predicates: Vec<String>) -> DataFrame {
         DataFrame {
             session: self.session,
             plan: Box::new(plan::LoadPlan {
@@ -63,57 +190,647 @@ impl DataFrameReader {
                 format: self.format,
                 options: self.options,
                 schema: self.schema,
+                predicates,
             }),
         }
     }
 }
 
+/// Marks `df` as small enough to be broadcast to all the executors when
+/// joined.
+pub fn broadcast(df: &DataFrame) -> DataFrame {
+    df.hint("broadcast", vec![])
+}
+
+/// Schema accepted by `DataFrameReader::schema`, formatted as DDL.
+pub trait ReaderSchema {
+    fn into_ddl(self) -> Result<Option<String>, SparkError>;
+}
+
+impl ReaderSchema for Option<String> {
+    fn into_ddl(self) -> Result<Option<String>, SparkError> {
+        Ok(self)
+    }
+}
+
+impl ReaderSchema for String {
+    fn into_ddl(self) -> Result<Option<String>, SparkError> {
+        Ok(Some(self))
+    }
+}
+
+impl ReaderSchema for &str {
+    fn into_ddl(self) -> Result<Option<String>, SparkError> {
+        Ok(Some(self.to_string()))
+    }
+}
+
+impl ReaderSchema for spark::DataType {
+    fn into_ddl(self) -> Result<Option<String>, SparkError> {
+        (&self).into_ddl()
+    }
+}
+
+impl ReaderSchema for &spark::DataType {
+    fn into_ddl(self) -> Result<Option<String>, SparkError> {
+        types::to_ddl(self).map(Some)
+    }
+}
+
 impl DataFrame {
     pub async fn collect(&self) -> Result<Vec<RecordBatch>, SparkError> {
         let rows = self.session.fetch(self.plan.collect()).await?;
         Ok(rows)
     }
 
-    pub fn select(&self, attrs: Vec<String>) -> DataFrame {
-        self.project(attrs, |attr| {
-            ExprType::UnresolvedAttribute(spark::expression::UnresolvedAttribute {
-                unparsed_identifier: attr,
-                plan_id: None,
+    /// Collects the rows deserialized into `T`, see `de::from_record_batches`
+    /// for how Spark types map onto Rust types.
+    pub async fn collect_as<T: DeserializeOwned>(&self) -> Result<Vec<T>, SparkError> {
+        let batches = self.collect().await?;
+        de::from_record_batches(&batches)
+    }
+
+    /// Like `collect_as`, but yields the rows as the batches arrive.
+    pub async fn to_stream_as<T: DeserializeOwned>(
+        &self,
+    ) -> Result<impl Stream<Item = Result<T, SparkError>>, SparkError> {
+        let batches = self
+            .session
+            .clone()
+            .fetch_stream(self.plan.collect())
+            .await?;
+        Ok(batches
+            .and_then(|batch| {
+                future::ready(
+                    de::from_record_batch::<T>(&batch)
+                        .map(|rows| stream::iter(rows.into_iter().map(Ok))),
+                )
             })
-        })
+            .try_flatten())
+    }
+
+    /// Like `collect`, but also returns the metrics of the physical operators
+    /// that executed the query.
+    pub async fn collect_with_metrics(&self) -> Result<QueryResult, SparkError> {
+        self.session.fetch_with_metrics(self.plan.collect()).await
+    }
+
+    /// Selects columns, given either as `Column`s or as column names.
+    pub fn select<C: Into<Column>>(&self, columns: Vec<C>) -> DataFrame {
+        self.project(
+            columns
+                .into_iter()
+                .map(|column| column.into().expression)
+                .collect(),
+        )
     }
 
     pub fn select_expr(&self, exprs: Vec<String>) -> DataFrame {
-        self.project(exprs, |expr| {
-            ExprType::ExpressionString(spark::expression::ExpressionString { expression: expr })
-        })
+        self.project(
+            exprs
+                .into_iter()
+                .map(|expr| spark::Expression {
+                    expr_type: Some(ExprType::ExpressionString(
+                        spark::expression::ExpressionString { expression: expr },
+                    )),
+                })
+                .collect(),
+        )
+    }
+
+    /// Keeps the rows for which `condition` is true.
+    pub fn filter(&self, condition: Column) -> DataFrame {
+        self.with_plan(Box::new(plan::Filter {
+            condition: condition.expression,
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Samples a fraction of the rows, without guarantee on the exact number
+    /// of rows returned. `seed` makes the sample reproducible.
+    pub fn sample(&self, fraction: f64, with_replacement: bool, seed: Option<i64>) -> DataFrame {
+        self.sample_between(0.0, fraction, with_replacement, seed, false)
     }
 
-    fn project(
+    /// Splits the rows in `weights.len()` `DataFrame`s, each with a share of
+    /// the rows proportional to its weight. The weights must be finite and
+    /// non-negative, and are normalized when they do not sum up to 1.
+    pub fn random_split(
         &self,
-        attrs: Vec<String>,
-        builder: fn(String) -> spark::expression::ExprType,
-    ) -> DataFrame {
-        let expressions = attrs
+        weights: Vec<f64>,
+        seed: Option<i64>,
+    ) -> Result<Vec<DataFrame>, SparkError> {
+        if let Some(weight) = weights
             .iter()
-            .map(|attr| spark::Expression {
-                expr_type: Some(builder(attr.clone())),
+            .find(|weight| !weight.is_finite() || **weight < 0.0)
+        {
+            return Err(SparkError::InvalidArgument(InvalidArgumentError(format!(
+                "Weights must be finite and non-negative, got {}",
+                weight
+            ))));
+        }
+        let total: f64 = weights.iter().sum();
+        if !(total > 0.0 && total.is_finite()) {
+            return Err(SparkError::InvalidArgument(InvalidArgumentError(
+                "The sum of the weights must be positive".to_string(),
+            )));
+        }
+        // The splits must share the seed to partition the rows without overlap
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos() as i64)
+                .unwrap_or_default()
+        });
+        let mut lower_bound = 0.0;
+        Ok(weights
+            .iter()
+            .map(|weight| {
+                let upper_bound = lower_bound + weight / total;
+                let split = self.sample_between(lower_bound, upper_bound, false, Some(seed), true);
+                lower_bound = upper_bound;
+                split
+            })
+            .collect())
+    }
+
+    /// Stratified sample without replacement, keeping for each value of `col`
+    /// the given fraction of rows. Rows whose value is missing from
+    /// `fractions` are dropped.
+    pub fn sample_by<K: Into<Literal>>(
+        &self,
+        col: Column,
+        fractions: HashMap<K, f64>,
+        seed: Option<i64>,
+    ) -> DataFrame {
+        let fractions = fractions
+            .into_iter()
+            .map(|(stratum, fraction)| spark::stat_sample_by::Fraction {
+                stratum: Some(stratum.into()),
+                fraction,
             })
             .collect();
+        self.with_plan(Box::new(plan::SampleBy {
+            col: col.expression,
+            fractions,
+            seed,
+            input: self.plan.clone(),
+        }))
+    }
 
-        let plan = Box::new(plan::Project {
-            expressions,
+    fn sample_between(
+        &self,
+        lower_bound: f64,
+        upper_bound: f64,
+        with_replacement: bool,
+        seed: Option<i64>,
+        deterministic_order: bool,
+    ) -> DataFrame {
+        self.with_plan(Box::new(plan::Sample {
+            lower_bound,
+            upper_bound,
+            with_replacement,
+            seed,
+            deterministic_order,
             input: self.plan.clone(),
-        });
+        }))
+    }
+
+    /// Shuffles the rows into exactly `num_partitions` partitions.
+    pub fn repartition(&self, num_partitions: i32) -> DataFrame {
+        self.with_plan(Box::new(plan::Repartition {
+            num_partitions,
+            shuffle: true,
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Reduces the number of partitions to `num_partitions` without a
+    /// shuffle, merging existing partitions.
+    pub fn coalesce(&self, num_partitions: i32) -> DataFrame {
+        self.with_plan(Box::new(plan::Repartition {
+            num_partitions,
+            shuffle: false,
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Hash partitions the rows by `cols`. The number of partitions defaults
+    /// to `spark.sql.shuffle.partitions`.
+    pub fn repartition_by(&self, num_partitions: Option<i32>, cols: Vec<Column>) -> DataFrame {
+        self.repartition_by_expression(num_partitions, cols)
+    }
+
+    /// Range partitions the rows by `sort_cols`, sorted in ascending order
+    /// unless given as sort orders with e.g. `Column::desc`.
+    pub fn repartition_by_range(
+        &self,
+        num_partitions: Option<i32>,
+        sort_cols: Vec<Column>,
+    ) -> DataFrame {
+        self.repartition_by_expression(
+            num_partitions,
+            sort_cols
+                .into_iter()
+                .map(|col| Column::from(ExprType::SortOrder(Box::new(col.into_sort_order()))))
+                .collect(),
+        )
+    }
+
+    fn repartition_by_expression(
+        &self,
+        num_partitions: Option<i32>,
+        cols: Vec<Column>,
+    ) -> DataFrame {
+        self.with_plan(Box::new(plan::RepartitionByExpression {
+            partition_exprs: cols.into_iter().map(|col| col.expression).collect(),
+            num_partitions,
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Names the `DataFrame`, so that its columns can be referenced with
+    /// `name` as qualifier, e.g. `col("a.id")`.
+    pub fn alias(&self, name: &str) -> DataFrame {
+        self.with_plan(Box::new(plan::SubqueryAlias {
+            alias: name.to_string(),
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Joins with `other` on `condition`. Columns of both sides can be told
+    /// apart by aliasing them, e.g. `df.alias("a").join(&df.alias("b"),
+    /// expr("a.manager = b.name"), JoinType::Inner)` for a self-join.
+    pub fn join(&self, other: &DataFrame, condition: Column, join_type: JoinType) -> DataFrame {
+        self.with_plan(Box::new(plan::Join {
+            left: self.plan.clone(),
+            right: other.plan.clone(),
+            join_condition: Some(condition.expression),
+            join_type,
+        }))
+    }
+
+    /// Cartesian product with `other`.
+    pub fn cross_join(&self, other: &DataFrame) -> DataFrame {
+        self.with_plan(Box::new(plan::Join {
+            left: self.plan.clone(),
+            right: other.plan.clone(),
+            join_condition: None,
+            join_type: JoinType::Cross,
+        }))
+    }
+
+    /// Gives a hint to the optimizer, such as `broadcast`, `merge`,
+    /// `repartition` or `rebalance`, with its parameters.
+    pub fn hint(&self, name: &str, parameters: Vec<Column>) -> DataFrame {
+        self.with_plan(Box::new(plan::Hint {
+            name: name.to_string(),
+            parameters: parameters.into_iter().map(|col| col.expression).collect(),
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Turns the `values` columns into rows, from wide to long format: each
+    /// row of the result holds the `ids` columns, the name of one of the
+    /// `values` columns as `variable_column_name` and its value as
+    /// `value_column_name`. When `values` is `None`, all the columns that are
+    /// not in `ids` are unpivoted.
+    pub fn unpivot(
+        &self,
+        ids: Vec<Column>,
+        values: Option<Vec<Column>>,
+        variable_column_name: &str,
+        value_column_name: &str,
+    ) -> DataFrame {
+        let expressions = |cols: Vec<Column>| -> Vec<spark::Expression> {
+            cols.into_iter().map(|col| col.expression).collect()
+        };
+        self.with_plan(Box::new(plan::Unpivot {
+            ids: expressions(ids),
+            values: values.map(expressions),
+            variable_column_name: variable_column_name.to_string(),
+            value_column_name: value_column_name.to_string(),
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Alias of `unpivot`.
+    pub fn melt(
+        &self,
+        ids: Vec<Column>,
+        values: Option<Vec<Column>>,
+        variable_column_name: &str,
+        value_column_name: &str,
+    ) -> DataFrame {
+        self.unpivot(ids, values, variable_column_name, value_column_name)
+    }
+
+    /// Computes the count, mean, standard deviation, min and max of `cols`,
+    /// or of all the numeric and string columns when empty.
+    pub fn describe(&self, cols: Vec<String>) -> DataFrame {
+        self.with_plan(Box::new(plan::Describe {
+            cols,
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Computes `statistics` for the numeric and string columns, e.g.
+    /// `count`, `mean`, `min`, `25%` or `count_distinct`. The default
+    /// statistics are those of `describe` plus the quartiles.
+    pub fn summary(&self, statistics: Vec<String>) -> DataFrame {
+        self.with_plan(Box::new(plan::Summary {
+            statistics,
+            input: self.plan.clone(),
+        }))
+    }
+
+    fn with_plan(&self, plan: Box<dyn plan::Plan>) -> DataFrame {
         DataFrame {
             session: self.session.clone(),
             plan,
         }
     }
 
+    /// Computes the aggregate expressions `exprs` while the `DataFrame` is
+    /// evaluated by an action, making their values available through
+    /// `observation` once the action completes. Fails when another pending
+    /// observation has the same name.
+    pub fn observe<C: Into<Column>>(
+        &self,
+        observation: &Observation,
+        exprs: Vec<C>,
+    ) -> Result<DataFrame, SparkError> {
+        let metrics = exprs
+            .into_iter()
+            .map(|expr| expr.into().expression)
+            .collect();
+        self.session.register_observation(observation)?;
+        Ok(self.with_plan(Box::new(plan::CollectMetrics {
+            name: observation.name().to_string(),
+            metrics,
+            input: self.plan.clone(),
+        })))
+    }
+
+    fn project(&self, expressions: Vec<spark::Expression>) -> DataFrame {
+        self.with_plan(Box::new(plan::Project {
+            expressions,
+            input: self.plan.clone(),
+        }))
+    }
+
+    /// Functions handling the missing values of the `DataFrame`.
+    pub fn na(&self) -> DataFrameNaFunctions {
+        DataFrameNaFunctions {
+            df: self.with_plan(self.plan.clone()),
+        }
+    }
+
+    /// Statistic functions over the columns of the `DataFrame`.
+    pub fn stat(&self) -> DataFrameStatFunctions {
+        DataFrameStatFunctions {
+            df: self.with_plan(self.plan.clone()),
+        }
+    }
+
     pub fn write(&self) -> DataFrameWriter {
         DataFrameWriter::new(self.plan.clone(), self.session.clone())
     }
+
+    /// Writes to the table `table_name` through the V2 data source API.
+    pub fn write_to(&self, table_name: String) -> DataFrameWriterV2 {
+        DataFrameWriterV2::new(table_name, self.plan.clone(), self.session.clone())
+    }
+}
+
+/// Value replacing missing values, see `DataFrame::na`. Spark only fills
+/// and replaces longs, doubles, strings and booleans, so narrower numbers
+/// are widened.
+pub trait NaValue {
+    fn into_literal(self) -> Literal;
+}
+
+impl NaValue for bool {
+    fn into_literal(self) -> Literal {
+        Literal::from(self)
+    }
+}
+
+impl NaValue for i32 {
+    fn into_literal(self) -> Literal {
+        Literal::from(i64::from(self))
+    }
+}
+
+impl NaValue for i64 {
+    fn into_literal(self) -> Literal {
+        Literal::from(self)
+    }
+}
+
+impl NaValue for f32 {
+    fn into_literal(self) -> Literal {
+        Literal::from(f64::from(self))
+    }
+}
+
+impl NaValue for f64 {
+    fn into_literal(self) -> Literal {
+        Literal::from(self)
+    }
+}
+
+impl NaValue for &str {
+    fn into_literal(self) -> Literal {
+        Literal::from(self)
+    }
+}
+
+impl NaValue for String {
+    fn into_literal(self) -> Literal {
+        Literal::from(self)
+    }
+}
+
+/// Lets `fill_map` mix the types of the values.
+impl NaValue for Literal {
+    fn into_literal(self) -> Literal {
+        match self.literal_type {
+            Some(
+                LiteralType::Byte(value) | LiteralType::Short(value) | LiteralType::Integer(value),
+            ) => value.into_literal(),
+            Some(LiteralType::Float(value)) => value.into_literal(),
+            _ => self,
+        }
+    }
+}
+
+/// Which rows `DataFrameNaFunctions::drop` drops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropHow {
+    /// Rows with at least one missing value.
+    Any,
+    /// Rows with only missing values.
+    All,
+}
+
+/// Handles the missing values, null or NaN, of a `DataFrame`. The `subset`
+/// of the columns to consider defaults to all the columns when `None`.
+pub struct DataFrameNaFunctions {
+    df: DataFrame,
+}
+
+impl DataFrameNaFunctions {
+    /// Replaces the missing values by `value`, in the columns of a matching
+    /// type only.
+    pub fn fill<V: NaValue>(&self, value: V, subset: Option<Vec<String>>) -> DataFrame {
+        self.df.with_plan(Box::new(plan::NaFill {
+            cols: subset.unwrap_or_default(),
+            values: vec![value.into_literal()],
+            input: self.df.plan.clone(),
+        }))
+    }
+
+    /// Replaces the missing values of each column by its value in `values`.
+    pub fn fill_map<V: NaValue>(&self, values: HashMap<String, V>) -> DataFrame {
+        let (cols, values) = values
+            .into_iter()
+            .map(|(col, value)| (col, value.into_literal()))
+            .unzip();
+        self.df.with_plan(Box::new(plan::NaFill {
+            cols,
+            values,
+            input: self.df.plan.clone(),
+        }))
+    }
+
+    /// Drops the rows with missing values, or with less than `thresh`
+    /// non-missing values when set, regardless of `how`.
+    pub fn drop(
+        &self,
+        how: DropHow,
+        thresh: Option<i32>,
+        subset: Option<Vec<String>>,
+    ) -> DataFrame {
+        let min_non_nulls = match (thresh, how) {
+            (Some(thresh), _) => Some(thresh),
+            (None, DropHow::Any) => None,
+            (None, DropHow::All) => Some(1),
+        };
+        self.df.with_plan(Box::new(plan::NaDrop {
+            cols: subset.unwrap_or_default(),
+            min_non_nulls,
+            input: self.df.plan.clone(),
+        }))
+    }
+
+    /// Replaces each value of `to_replace` by the value at the same position
+    /// in `value`. Numbers only replace numbers, strings only strings and
+    /// booleans only booleans.
+    pub fn replace<O: NaValue, N: NaValue>(
+        &self,
+        to_replace: Vec<O>,
+        value: Vec<N>,
+        subset: Option<Vec<String>>,
+    ) -> Result<DataFrame, SparkError> {
+        if to_replace.len() != value.len() {
+            return Err(SparkError::InvalidArgument(InvalidArgumentError(format!(
+                "Got {} values to replace but {} replacements",
+                to_replace.len(),
+                value.len()
+            ))));
+        }
+        let replacements = to_replace
+            .into_iter()
+            .zip(value)
+            .map(|(old_value, new_value)| spark::na_replace::Replacement {
+                old_value: Some(old_value.into_literal()),
+                new_value: Some(new_value.into_literal()),
+            })
+            .collect();
+        Ok(self.df.with_plan(Box::new(plan::NaReplace {
+            cols: subset.unwrap_or_default(),
+            replacements,
+            input: self.df.plan.clone(),
+        })))
+    }
+}
+
+/// Statistic functions over the columns of a `DataFrame`, see
+/// `DataFrame::stat`.
+pub struct DataFrameStatFunctions {
+    df: DataFrame,
+}
+
+impl DataFrameStatFunctions {
+    /// Computes the frequency table of the pairs of values of `col1` and
+    /// `col2`, with a row per value of `col1` and a column per value of
+    /// `col2`.
+    pub fn crosstab(&self, col1: &str, col2: &str) -> DataFrame {
+        self.df.with_plan(Box::new(plan::Crosstab {
+            col1: col1.to_string(),
+            col2: col2.to_string(),
+            input: self.df.plan.clone(),
+        }))
+    }
+
+    /// Finds the values of `cols` appearing in at least a `support` fraction
+    /// of the rows, 1% by default. The result may contain false positives.
+    pub fn freq_items(&self, cols: Vec<String>, support: Option<f64>) -> DataFrame {
+        self.df.with_plan(Box::new(plan::FreqItems {
+            cols,
+            support,
+            input: self.df.plan.clone(),
+        }))
+    }
+
+    /// Computes the approximate quantiles of each column of `cols` at
+    /// `probabilities`, within `relative_error`, 0 giving the exact
+    /// quantiles at a higher cost. Returns the quantiles of each column in
+    /// the order of `cols`.
+    pub async fn approx_quantile(
+        &self,
+        cols: Vec<String>,
+        probabilities: Vec<f64>,
+        relative_error: f64,
+    ) -> Result<Vec<Vec<f64>>, SparkError> {
+        self.fetch_scalar(Box::new(plan::ApproxQuantile {
+            cols,
+            probabilities,
+            relative_error,
+            input: self.df.plan.clone(),
+        }))
+        .await
+    }
+
+    /// Computes the Pearson correlation coefficient of `col1` and `col2`.
+    pub async fn corr(&self, col1: &str, col2: &str) -> Result<f64, SparkError> {
+        self.fetch_scalar(Box::new(plan::Corr {
+            col1: col1.to_string(),
+            col2: col2.to_string(),
+            method: None,
+            input: self.df.plan.clone(),
+        }))
+        .await
+    }
+
+    /// Computes the sample covariance of `col1` and `col2`.
+    pub async fn cov(&self, col1: &str, col2: &str) -> Result<f64, SparkError> {
+        self.fetch_scalar(Box::new(plan::Cov {
+            col1: col1.to_string(),
+            col2: col2.to_string(),
+            input: self.df.plan.clone(),
+        }))
+        .await
+    }
+
+    /// Scalar statistics are answered with a one row, one column result.
+    async fn fetch_scalar<T: DeserializeOwned>(
+        &self,
+        plan: Box<dyn plan::Plan>,
+    ) -> Result<T, SparkError> {
+        self.df.session.fetch_scalar(plan.collect()).await
+    }
 }
 
 pub struct DataFrameWriter {
@@ -140,8 +857,25 @@ impl DataFrameWriter {
         }
     }
 
-    pub fn bucket_by(mut self, bucket_by: write_operation::BucketBy) -> Self {
-        self.bucket_by = Some(bucket_by);
+    /// Buckets the output by `cols` into `num_buckets` buckets, only
+    /// supported when saving as a table.
+    pub fn bucket_by(mut self, num_buckets: i32, cols: Vec<String>) -> Self {
+        self.bucket_by = Some(write_operation::BucketBy {
+            bucket_column_names: cols,
+            num_buckets,
+        });
+        self
+    }
+
+    /// Sorts the rows of each bucket by `cols`, see `bucket_by`.
+    pub fn sort_by(mut self, cols: Vec<String>) -> Self {
+        self.sort_column_names = cols;
+        self
+    }
+
+    /// Partitions the output on the file system by `cols`.
+    pub fn partition_by(mut self, cols: Vec<String>) -> Self {
+        self.partitioning_columns = cols;
         self
     }
 
@@ -155,18 +889,60 @@ impl DataFrameWriter {
         self
     }
 
-    pub fn options(mut self, options: HashMap<String, String>) -> Self {
-        self.options = options;
+    /// Adds the options, given as a map or as one of the typed options of
+    /// the `options` module, to the ones already set.
+    pub fn options<O: Into<HashMap<String, String>>>(mut self, options: O) -> Self {
+        self.options.extend(options.into());
+        self
+    }
+
+    pub fn option(mut self, key: String, value: String) -> Self {
+        self.options.insert(key, value);
         self
     }
 
-    /// save takes one parameter, save_type, which should be not set if the
-    /// destination is neither a path nor a table, such as jdbc and noop,
+    /// Saves the `DataFrame` at `path`, which should be `None` when the
+    /// destination is not a path, such as with the `jdbc` and `noop` formats.
+    pub async fn save(self, path: Option<String>) -> Result<(), SparkError> {
+        self.execute(path.map(write_operation::SaveType::Path))
+            .await
+    }
+
+    /// Saves the `DataFrame` as the table `table_name`, honoring the save
+    /// mode when the table already exists.
+    pub async fn save_as_table(self, table_name: String) -> Result<(), SparkError> {
+        self.save_table(table_name, TableSaveMethod::SaveAsTable)
+            .await
+    }
+
+    /// Inserts the rows into the existing table `table_name`, matching
+    /// columns by position, replacing its content when `overwrite` is true.
+    pub async fn insert_into(self, table_name: String, overwrite: bool) -> Result<(), SparkError> {
+        let mode = if overwrite {
+            write_operation::SaveMode::Overwrite
+        } else {
+            write_operation::SaveMode::Append
+        };
+        self.mode(mode)
+            .save_table(table_name, TableSaveMethod::InsertInto)
+            .await
+    }
 
-    pub async fn save(
+    async fn save_table(
         self,
-        save_type: Option<write_operation::SaveType>,
+        table_name: String,
+        save_method: TableSaveMethod,
     ) -> Result<(), SparkError> {
+        self.execute(Some(write_operation::SaveType::Table(
+            write_operation::SaveTable {
+                table_name,
+                save_method: save_method.into(),
+            },
+        )))
+        .await
+    }
+
+    async fn execute(self, save_type: Option<write_operation::SaveType>) -> Result<(), SparkError> {
         self.session
             .save(spark::WriteOperation {
                 input: Some(self.plan.collect()),
@@ -182,3 +958,109 @@ impl DataFrameWriter {
         Ok(())
     }
 }
+
+pub struct DataFrameWriterV2 {
+    options: HashMap<String, String>,
+    partitioning_columns: Vec<spark::Expression>,
+    plan: Box<dyn plan::Plan>,
+    provider: Option<String>,
+    session: Rc<session::RemoteSparkSession>,
+    table_name: String,
+    table_properties: HashMap<String, String>,
+}
+
+impl DataFrameWriterV2 {
+    pub fn new(
+        table_name: String,
+        plan: Box<dyn plan::Plan>,
+        session: Rc<session::RemoteSparkSession>,
+    ) -> Self {
+        DataFrameWriterV2 {
+            options: HashMap::new(),
+            partitioning_columns: vec![],
+            plan,
+            provider: None,
+            session,
+            table_name,
+            table_properties: HashMap::new(),
+        }
+    }
+
+    /// Sets the provider of the table, e.g. `iceberg` or `delta`, when it is
+    /// created or replaced.
+    pub fn using(mut self, provider: String) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    pub fn option(mut self, key: String, value: String) -> Self {
+        self.options.insert(key, value);
+        self
+    }
+
+    pub fn table_property(mut self, key: String, value: String) -> Self {
+        self.table_properties.insert(key, value);
+        self
+    }
+
+    /// Partitions the table created or replaced by `cols`, which may be
+    /// partition transforms such as `years(ts)`.
+    pub fn partitioned_by(mut self, cols: Vec<Column>) -> Self {
+        self.partitioning_columns = cols.into_iter().map(|col| col.expression).collect();
+        self
+    }
+
+    /// Creates the table, failing if it already exists.
+    pub async fn create(self) -> Result<(), SparkError> {
+        self.execute(write_operation_v2::Mode::Create, None).await
+    }
+
+    /// Atomically replaces the table, failing if it does not exist.
+    pub async fn replace(self) -> Result<(), SparkError> {
+        self.execute(write_operation_v2::Mode::Replace, None).await
+    }
+
+    pub async fn create_or_replace(self) -> Result<(), SparkError> {
+        self.execute(write_operation_v2::Mode::CreateOrReplace, None)
+            .await
+    }
+
+    pub async fn append(self) -> Result<(), SparkError> {
+        self.execute(write_operation_v2::Mode::Append, None).await
+    }
+
+    /// Atomically deletes the rows matching `condition` and appends the
+    /// content of the `DataFrame`.
+    pub async fn overwrite(self, condition: Column) -> Result<(), SparkError> {
+        self.execute(write_operation_v2::Mode::Overwrite, Some(condition))
+            .await
+    }
+
+    /// Replaces the partitions of the table for which the `DataFrame` has
+    /// at least one row.
+    pub async fn overwrite_partitions(self) -> Result<(), SparkError> {
+        self.execute(write_operation_v2::Mode::OverwritePartitions, None)
+            .await
+    }
+
+    async fn execute(
+        self,
+        mode: write_operation_v2::Mode,
+        overwrite_condition: Option<Column>,
+    ) -> Result<(), SparkError> {
+        self.session
+            .execute_command(spark::command::CommandType::WriteOperationV2(
+                spark::WriteOperationV2 {
+                    input: Some(self.plan.collect()),
+                    table_name: self.table_name,
+                    provider: self.provider,
+                    partitioning_columns: self.partitioning_columns,
+                    options: self.options,
+                    table_properties: self.table_properties,
+                    mode: mode.into(),
+                    overwrite_condition: overwrite_condition.map(|col| col.expression),
+                },
+            ))
+            .await
+    }
+}
@@ -0,0 +1,548 @@
+use crate::error::{DeserializationError, SparkError};
+use arrow::array::{Array, ArrayRef, AsArray};
+use arrow::datatypes::*;
+use arrow::record_batch::RecordBatch;
+use arrow::util::display::array_value_to_string;
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::fmt;
+
+/// Deserializes each row of the batches into `T`.
+///
+/// Rows map onto structs by column name or onto tuples by position. Nested
+/// Spark types map onto `Vec` (arrays), `HashMap` (maps), nested structs
+/// and `Option` (nullable values). Decimals, dates and timestamps are
+/// exposed as strings, so that types like `chrono::NaiveDate` can parse
+/// them, or as their raw integer representation when one is requested.
+pub fn from_record_batches<T: DeserializeOwned>(
+    batches: &[RecordBatch],
+) -> Result<Vec<T>, SparkError> {
+    let mut rows = Vec::new();
+    for batch in batches {
+        rows.extend(from_record_batch(batch)?);
+    }
+    Ok(rows)
+}
+
+pub fn from_record_batch<T: DeserializeOwned>(batch: &RecordBatch) -> Result<Vec<T>, SparkError> {
+    let schema = batch.schema();
+    (0..batch.num_rows())
+        .map(|row| {
+            let deserializer = StructDeserializer {
+                fields: schema.fields(),
+                columns: batch.columns(),
+                row,
+                path: "",
+            };
+            T::deserialize(deserializer).map_err(SparkError::from)
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+struct Error {
+    path: Option<String>,
+    message: String,
+}
+
+impl Error {
+    /// Records the path of the innermost value that failed.
+    fn at(mut self, path: &str) -> Self {
+        if self.path.is_none() {
+            self.path = Some(path.to_string());
+        }
+        self
+    }
+}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error {
+            path: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "Cannot deserialize column {}: {}", path, self.message),
+            None => write!(f, "Cannot deserialize row: {}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for SparkError {
+    fn from(err: Error) -> Self {
+        SparkError::DeserializationFailed(DeserializationError(err.to_string()))
+    }
+}
+
+fn child_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Deserializes a row of a batch, or a value of a struct column.
+struct StructDeserializer<'a> {
+    fields: &'a Fields,
+    columns: &'a [ArrayRef],
+    row: usize,
+    path: &'a str,
+}
+
+impl<'de, 'a> de::Deserializer<'de> for StructDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(FieldsAccess {
+            inner: self,
+            index: 0,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(FieldsAccess {
+            inner: self,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+struct FieldsAccess<'a> {
+    inner: StructDeserializer<'a>,
+    index: usize,
+}
+
+impl<'a> FieldsAccess<'a> {
+    fn next_value<'de, T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<T::Value, Error> {
+        let field = &self.inner.fields[self.index];
+        let path = child_path(self.inner.path, field.name());
+        let value = seed
+            .deserialize(ValueDeserializer {
+                array: self.inner.columns[self.index].as_ref(),
+                row: self.inner.row,
+                path: &path,
+            })
+            .map_err(|err| err.at(&path));
+        self.index += 1;
+        value
+    }
+}
+
+impl<'de, 'a> MapAccess<'de> for FieldsAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.inner.fields.get(self.index) {
+            Some(field) => {
+                let name: de::value::StrDeserializer<Error> =
+                    field.name().as_str().into_deserializer();
+                seed.deserialize(name).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        self.next_value(seed)
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for FieldsAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index >= self.inner.columns.len() {
+            return Ok(None);
+        }
+        self.next_value(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.inner.columns.len() - self.index)
+    }
+}
+
+/// Deserializes the elements of an array value.
+struct ElementsAccess<'a> {
+    values: ArrayRef,
+    index: usize,
+    path: &'a str,
+}
+
+impl<'de, 'a> SeqAccess<'de> for ElementsAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        if self.index >= self.values.len() {
+            return Ok(None);
+        }
+        let path = format!("{}[{}]", self.path, self.index);
+        let value = seed
+            .deserialize(ValueDeserializer {
+                array: self.values.as_ref(),
+                row: self.index,
+                path: &path,
+            })
+            .map_err(|err| err.at(&path));
+        self.index += 1;
+        value.map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len() - self.index)
+    }
+}
+
+/// Deserializes the entries of a map value.
+struct EntriesAccess<'a> {
+    keys: ArrayRef,
+    values: ArrayRef,
+    index: usize,
+    path: &'a str,
+}
+
+impl<'de, 'a> MapAccess<'de> for EntriesAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        if self.index >= self.keys.len() {
+            return Ok(None);
+        }
+        let path = format!("{}.key", self.path);
+        seed.deserialize(ValueDeserializer {
+            array: self.keys.as_ref(),
+            row: self.index,
+            path: &path,
+        })
+        .map(Some)
+        .map_err(|err| err.at(&path))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let path = format!("{}.value", self.path);
+        let value = seed
+            .deserialize(ValueDeserializer {
+                array: self.values.as_ref(),
+                row: self.index,
+                path: &path,
+            })
+            .map_err(|err| err.at(&path));
+        self.index += 1;
+        value
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.keys.len() - self.index)
+    }
+}
+
+/// Deserializes a single value of a column.
+struct ValueDeserializer<'a> {
+    array: &'a dyn Array,
+    row: usize,
+    path: &'a str,
+}
+
+impl<'a> ValueDeserializer<'a> {
+    fn to_string(&self) -> Result<String, Error> {
+        array_value_to_string(self.array, self.row).map_err(de::Error::custom)
+    }
+
+    /// Raw integer representation of temporal values.
+    fn temporal_value(&self) -> Option<i64> {
+        let (array, row) = (self.array, self.row);
+        let value = match array.data_type() {
+            DataType::Date32 => array.as_primitive::<Date32Type>().value(row) as i64,
+            DataType::Date64 => array.as_primitive::<Date64Type>().value(row),
+            DataType::Timestamp(TimeUnit::Second, _) => {
+                array.as_primitive::<TimestampSecondType>().value(row)
+            }
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                array.as_primitive::<TimestampMillisecondType>().value(row)
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                array.as_primitive::<TimestampMicrosecondType>().value(row)
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+                array.as_primitive::<TimestampNanosecondType>().value(row)
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    fn unsupported<T>(&self) -> Result<T, Error> {
+        Err(de::Error::custom(format!(
+            "unsupported data type {}",
+            self.array.data_type()
+        )))
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for ValueDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let (array, row) = (self.array, self.row);
+        if array.is_null(row) {
+            return visitor.visit_none();
+        }
+        match array.data_type() {
+            DataType::Null => visitor.visit_unit(),
+            DataType::Boolean => visitor.visit_bool(array.as_boolean().value(row)),
+            DataType::Int8 => visitor.visit_i8(array.as_primitive::<Int8Type>().value(row)),
+            DataType::Int16 => visitor.visit_i16(array.as_primitive::<Int16Type>().value(row)),
+            DataType::Int32 => visitor.visit_i32(array.as_primitive::<Int32Type>().value(row)),
+            DataType::Int64 => visitor.visit_i64(array.as_primitive::<Int64Type>().value(row)),
+            DataType::UInt8 => visitor.visit_u8(array.as_primitive::<UInt8Type>().value(row)),
+            DataType::UInt16 => visitor.visit_u16(array.as_primitive::<UInt16Type>().value(row)),
+            DataType::UInt32 => visitor.visit_u32(array.as_primitive::<UInt32Type>().value(row)),
+            DataType::UInt64 => visitor.visit_u64(array.as_primitive::<UInt64Type>().value(row)),
+            DataType::Float32 => visitor.visit_f32(array.as_primitive::<Float32Type>().value(row)),
+            DataType::Float64 => visitor.visit_f64(array.as_primitive::<Float64Type>().value(row)),
+            DataType::Utf8 => visitor.visit_str(array.as_string::<i32>().value(row)),
+            DataType::LargeUtf8 => visitor.visit_str(array.as_string::<i64>().value(row)),
+            DataType::Binary => visitor.visit_bytes(array.as_binary::<i32>().value(row)),
+            DataType::LargeBinary => visitor.visit_bytes(array.as_binary::<i64>().value(row)),
+            DataType::Decimal128(_, _)
+            | DataType::Decimal256(_, _)
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Timestamp(_, _) => visitor.visit_string(self.to_string()?),
+            DataType::Interval(IntervalUnit::YearMonth) => {
+                visitor.visit_i32(array.as_primitive::<IntervalYearMonthType>().value(row))
+            }
+            DataType::Duration(TimeUnit::Microsecond) => {
+                visitor.visit_i64(array.as_primitive::<DurationMicrosecondType>().value(row))
+            }
+            DataType::List(_) => visitor.visit_seq(ElementsAccess {
+                values: array.as_list::<i32>().value(row),
+                index: 0,
+                path: self.path,
+            }),
+            DataType::LargeList(_) => visitor.visit_seq(ElementsAccess {
+                values: array.as_list::<i64>().value(row),
+                index: 0,
+                path: self.path,
+            }),
+            DataType::Struct(_) => {
+                let st = array.as_struct();
+                StructDeserializer {
+                    fields: st.fields(),
+                    columns: st.columns(),
+                    row,
+                    path: self.path,
+                }
+                .deserialize_any(visitor)
+            }
+            DataType::Map(_, _) => {
+                let entries = array.as_map().value(row);
+                visitor.visit_map(EntriesAccess {
+                    keys: entries.column(0).clone(),
+                    values: entries.column(1).clone(),
+                    index: 0,
+                    path: self.path,
+                })
+            }
+            _ => self.unsupported(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.array.is_null(self.row) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.array.data_type() {
+            DataType::Date32 if !self.array.is_null(self.row) => {
+                visitor.visit_i32(self.array.as_primitive::<Date32Type>().value(self.row))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.temporal_value() {
+            Some(value) if !self.array.is_null(self.row) => visitor.visit_i64(value),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.array.data_type() {
+            DataType::Decimal128(_, _) if !self.array.is_null(self.row) => {
+                let value = self.to_string()?;
+                visitor.visit_f64(value.parse().map_err(de::Error::custom)?)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.array.data_type() {
+            DataType::Binary if !self.array.is_null(self.row) => {
+                visitor.visit_byte_buf(self.array.as_binary::<i32>().value(self.row).to_vec())
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are read from string columns.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.array.data_type() {
+            DataType::Utf8 if !self.array.is_null(self.row) => {
+                let variant: de::value::StrDeserializer<Error> = self
+                    .array
+                    .as_string::<i32>()
+                    .value(self.row)
+                    .into_deserializer();
+                visitor.visit_enum(variant)
+            }
+            _ => self.unsupported(),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i128 u8 u16 u32 u64 u128 f32 char str string bytes
+        unit unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, ListBuilder, StringArray, StringBuilder, StructArray};
+    use serde::Deserialize;
+    use std::sync::Arc;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Address {
+        city: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Employee {
+        name: String,
+        salary: Option<i64>,
+        skills: Vec<String>,
+        address: Address,
+    }
+
+    fn employees() -> RecordBatch {
+        let mut skills = ListBuilder::new(StringBuilder::new());
+        skills.values().append_value("rust");
+        skills.append(true);
+        let address = StructArray::from(vec![(
+            Arc::new(Field::new("city", DataType::Utf8, false)),
+            Arc::new(StringArray::from(vec!["Milan"])) as ArrayRef,
+        )]);
+        RecordBatch::try_from_iter(vec![
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["Michael"])) as ArrayRef,
+            ),
+            ("salary", Arc::new(Int64Array::from(vec![None])) as ArrayRef),
+            ("skills", Arc::new(skills.finish()) as ArrayRef),
+            ("address", Arc::new(address) as ArrayRef),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_deserialize_nested_rows() {
+        let rows: Vec<Employee> = from_record_batch(&employees()).unwrap();
+        assert_eq!(
+            rows,
+            vec![Employee {
+                name: "Michael".to_string(),
+                salary: None,
+                skills: vec!["rust".to_string()],
+                address: Address {
+                    city: "Milan".to_string()
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_type_mismatch_reports_column_path() {
+        #[derive(Debug, Deserialize)]
+        struct WrongAddress {
+            #[allow(dead_code)]
+            city: i64,
+        }
+        #[derive(Debug, Deserialize)]
+        struct WrongEmployee {
+            #[allow(dead_code)]
+            address: WrongAddress,
+        }
+        let err = from_record_batch::<WrongEmployee>(&employees()).unwrap_err();
+        assert!(err.to_string().contains("address.city"), "{}", err);
+    }
+}
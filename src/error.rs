@@ -33,6 +33,7 @@ pub enum SparkError {
     EmptyResponse,
     Generic(GenericError),
     HiveCatalogNotEnabled(HiveCatalogNotEnabledError),
+    InvalidArgument(InvalidArgumentError),
     InvalidSyntax(ParseSyntaxError),
     NotImplementedYet(NotImplementedYetError),
     Unexpected(UnexpectedError),
@@ -53,20 +54,20 @@ impl From<DeserializationError> for SparkError {
 impl fmt::Display for DeserializationError {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.message())
+        f.write_str(self.message().as_str())
     }
 }
 impl error::Error for DeserializationError {}
 
 impl DeserializationError {
     #[inline]
-    fn message(&self) -> &'static str {
-        "Table or view not found"
+    fn message(&self) -> String {
+        self.0.clone()
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct GenericError(Status);
+pub struct GenericError(Box<Status>);
 
 impl From<GenericError> for SparkError {
     #[inline]
@@ -81,7 +82,12 @@ impl fmt::Display for GenericError {
         f.write_str(self.message().as_str())
     }
 }
-impl error::Error for GenericError {}
+impl error::Error for GenericError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
 
 impl GenericError {
     #[inline]
@@ -91,7 +97,7 @@ impl GenericError {
 }
 
 #[derive(Debug, Clone)]
-pub struct HiveCatalogNotEnabledError(Status);
+pub struct HiveCatalogNotEnabledError(Box<Status>);
 
 impl From<HiveCatalogNotEnabledError> for SparkError {
     #[inline]
@@ -114,7 +120,38 @@ impl fmt::Display for HiveCatalogNotEnabledError {
     }
 }
 
-impl error::Error for HiveCatalogNotEnabledError {}
+impl error::Error for HiveCatalogNotEnabledError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvalidArgumentError(pub(crate) String);
+
+impl From<InvalidArgumentError> for SparkError {
+    #[inline]
+    fn from(err: InvalidArgumentError) -> Self {
+        Self::InvalidArgument(err)
+    }
+}
+
+impl InvalidArgumentError {
+    #[inline]
+    fn message(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl fmt::Display for InvalidArgumentError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message().as_str())
+    }
+}
+
+impl error::Error for InvalidArgumentError {}
 
 #[derive(Debug, Clone)]
 pub struct NotImplementedYetError(pub(crate) String);
@@ -143,7 +180,7 @@ impl fmt::Display for NotImplementedYetError {
 impl error::Error for NotImplementedYetError {}
 
 #[derive(Debug, Clone)]
-pub struct ParseSyntaxError(Status);
+pub struct ParseSyntaxError(Box<Status>);
 
 impl From<ParseSyntaxError> for SparkError {
     #[inline]
@@ -158,7 +195,12 @@ impl fmt::Display for ParseSyntaxError {
         f.write_str(self.message().as_str())
     }
 }
-impl error::Error for ParseSyntaxError {}
+impl error::Error for ParseSyntaxError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
 
 impl ParseSyntaxError {
     #[inline]
@@ -168,7 +210,7 @@ impl ParseSyntaxError {
 }
 
 #[derive(Debug, Clone)]
-pub struct TableOrViewNotFoundError(Status);
+pub struct TableOrViewNotFoundError(Box<Status>);
 
 impl From<TableOrViewNotFoundError> for SparkError {
     #[inline]
@@ -183,7 +225,12 @@ impl fmt::Display for TableOrViewNotFoundError {
         f.write_str(self.message())
     }
 }
-impl error::Error for TableOrViewNotFoundError {}
+impl error::Error for TableOrViewNotFoundError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
 
 impl TableOrViewNotFoundError {
     #[inline]
@@ -218,7 +265,7 @@ impl UnexpectedError {
 }
 
 #[derive(Debug, Clone)]
-pub struct UnresolvedColumnWithSuggestionError(Status);
+pub struct UnresolvedColumnWithSuggestionError(Box<Status>);
 
 impl From<UnresolvedColumnWithSuggestionError> for SparkError {
     #[inline]
@@ -233,7 +280,12 @@ impl fmt::Display for UnresolvedColumnWithSuggestionError {
         f.write_str(self.message().as_str())
     }
 }
-impl error::Error for UnresolvedColumnWithSuggestionError {}
+impl error::Error for UnresolvedColumnWithSuggestionError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&*self.0)
+    }
+}
 
 impl UnresolvedColumnWithSuggestionError {
     #[inline]
@@ -250,6 +302,7 @@ impl error::Error for SparkError {
             Self::EmptyResponse => None,
             Self::Generic(ref err) => Some(err),
             Self::HiveCatalogNotEnabled(ref err) => Some(err),
+            Self::InvalidArgument(ref err) => Some(err),
             Self::InvalidSyntax(ref err) => Some(err),
             Self::NotImplementedYet(ref err) => Some(err),
             Self::TableOrViewNotFound(ref err) => Some(err),
@@ -267,6 +320,7 @@ impl fmt::Display for SparkError {
             Self::EmptyResponse => f.write_str("Empty response"),
             Self::Generic(err) => err.fmt(f),
             Self::HiveCatalogNotEnabled(err) => err.fmt(f),
+            Self::InvalidArgument(ref err) => err.fmt(f),
             Self::InvalidSyntax(ref err) => err.fmt(f),
             Self::NotImplementedYet(ref err) => err.fmt(f),
             Self::TableOrViewNotFound(err) => err.fmt(f),
@@ -280,16 +334,18 @@ type ErrorFactory = fn(err: Status) -> SparkError;
 
 static ERROR_MAPPINGS: &[(&str, ErrorFactory)] = &[
     ("[TABLE_OR_VIEW_NOT_FOUND]", |err| {
-        SparkError::TableOrViewNotFound(TableOrViewNotFoundError(err))
+        SparkError::TableOrViewNotFound(TableOrViewNotFoundError(Box::new(err)))
     }),
     ("[HIVE_CATALOG_NOT_ENABLED]", |err| {
-        SparkError::HiveCatalogNotEnabled(HiveCatalogNotEnabledError(err))
+        SparkError::HiveCatalogNotEnabled(HiveCatalogNotEnabledError(Box::new(err)))
     }),
     ("[PARSE_SYNTAX_ERROR]", |err| {
-        SparkError::InvalidSyntax(ParseSyntaxError(err))
+        SparkError::InvalidSyntax(ParseSyntaxError(Box::new(err)))
     }),
     ("[UNRESOLVED_COLUMN.WITH_SUGGESTION]", |err| {
-        SparkError::UnresolvedColumnWithSuggestion(UnresolvedColumnWithSuggestionError(err))
+        SparkError::UnresolvedColumnWithSuggestion(UnresolvedColumnWithSuggestionError(Box::new(
+            err,
+        )))
     }),
 ];
 
@@ -301,6 +357,6 @@ impl From<Status> for SparkError {
                 return error_constructor(err);
             }
         }
-        Self::Generic(GenericError(err))
+        Self::Generic(GenericError(Box::new(err)))
     }
 }
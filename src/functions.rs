@@ -0,0 +1,905 @@
+//! Built-in functions of Spark SQL, resolved by name on the server.
+//!
+//! Expression arguments accept anything convertible into a `Column`: strings
+//! name columns, while `lit` passes a value, e.g.
+//! `array_contains("tags", lit("spark"))`. Functions taking a variable
+//! number of expressions take a `Vec`.
+use crate::column::Column;
+use crate::spark;
+use crate::spark::expression::ExprType;
+use crate::spark::expression::Literal;
+use std::cell::Cell;
+use std::collections::HashMap;
+
+fn call(name: &str, args: Vec<Column>) -> Column {
+    call_function(name, args, false)
+}
+
+fn call_distinct(name: &str, args: Vec<Column>) -> Column {
+    call_function(name, args, true)
+}
+
+fn call_function(name: &str, args: Vec<Column>, is_distinct: bool) -> Column {
+    Column::from(ExprType::UnresolvedFunction(
+        spark::expression::UnresolvedFunction {
+            function_name: name.to_string(),
+            arguments: args.into_iter().map(|arg| arg.expression).collect(),
+            is_distinct,
+            is_user_defined_function: false,
+        },
+    ))
+}
+
+/// Literal `value`, e.g. `lit(42)`, `lit("spark")` or `lit(vec![1, 2])`, see
+/// `literal` for the supported types.
+pub fn lit<T: Into<Literal>>(value: T) -> Column {
+    Column::from(value.into())
+}
+
+fn columns<C: Into<Column>>(cols: Vec<C>) -> Vec<Column> {
+    cols.into_iter().map(Into::into).collect()
+}
+
+// String functions
+
+pub fn upper(col: impl Into<Column>) -> Column {
+    call("upper", vec![col.into()])
+}
+
+pub fn lower(col: impl Into<Column>) -> Column {
+    call("lower", vec![col.into()])
+}
+
+pub fn trim(col: impl Into<Column>) -> Column {
+    call("trim", vec![col.into()])
+}
+
+pub fn ltrim(col: impl Into<Column>) -> Column {
+    call("ltrim", vec![col.into()])
+}
+
+pub fn rtrim(col: impl Into<Column>) -> Column {
+    call("rtrim", vec![col.into()])
+}
+
+/// Number of characters of a string, or of bytes of a binary.
+pub fn length(col: impl Into<Column>) -> Column {
+    call("length", vec![col.into()])
+}
+
+/// Reverses a string, or the elements of an array.
+pub fn reverse(col: impl Into<Column>) -> Column {
+    call("reverse", vec![col.into()])
+}
+
+/// Capitalizes the first letter of each word.
+pub fn initcap(col: impl Into<Column>) -> Column {
+    call("initcap", vec![col.into()])
+}
+
+pub fn ascii(col: impl Into<Column>) -> Column {
+    call("ascii", vec![col.into()])
+}
+
+/// Concatenates strings, binaries or arrays.
+pub fn concat<C: Into<Column>>(cols: Vec<C>) -> Column {
+    call("concat", columns(cols))
+}
+
+/// Concatenates strings with `separator`, skipping nulls.
+pub fn concat_ws<C: Into<Column>>(separator: &str, cols: Vec<C>) -> Column {
+    let mut args = vec![lit(separator)];
+    args.extend(columns(cols));
+    call("concat_ws", args)
+}
+
+/// Substring of `len` characters starting at `pos`, counted from 1, or from
+/// the end of the string when negative.
+pub fn substring(col: impl Into<Column>, pos: i32, len: i32) -> Column {
+    call("substring", vec![col.into(), lit(pos), lit(len)])
+}
+
+/// Splits a string around the matches of the regular expression `pattern`.
+pub fn split(col: impl Into<Column>, pattern: &str) -> Column {
+    call("split", vec![col.into(), lit(pattern)])
+}
+
+pub fn regexp_replace(col: impl Into<Column>, pattern: &str, replacement: &str) -> Column {
+    call(
+        "regexp_replace",
+        vec![col.into(), lit(pattern), lit(replacement)],
+    )
+}
+
+/// Extracts the group `idx` of the first match of `pattern`, the whole match
+/// when 0.
+pub fn regexp_extract(col: impl Into<Column>, pattern: &str, idx: i32) -> Column {
+    call("regexp_extract", vec![col.into(), lit(pattern), lit(idx)])
+}
+
+pub fn lpad(col: impl Into<Column>, len: i32, pad: &str) -> Column {
+    call("lpad", vec![col.into(), lit(len), lit(pad)])
+}
+
+pub fn rpad(col: impl Into<Column>, len: i32, pad: &str) -> Column {
+    call("rpad", vec![col.into(), lit(len), lit(pad)])
+}
+
+/// Position of the first occurrence of `substring`, counted from 1, or 0 when
+/// not found.
+pub fn instr(col: impl Into<Column>, substring: &str) -> Column {
+    call("instr", vec![col.into(), lit(substring)])
+}
+
+pub fn repeat(col: impl Into<Column>, n: i32) -> Column {
+    call("repeat", vec![col.into(), lit(n)])
+}
+
+/// Replaces each character of `matching` by the character at the same
+/// position in `replace`, or removes it when `replace` is shorter.
+pub fn translate(col: impl Into<Column>, matching: &str, replace: &str) -> Column {
+    call("translate", vec![col.into(), lit(matching), lit(replace)])
+}
+
+/// Formats the arguments printf-style.
+pub fn format_string<C: Into<Column>>(format: &str, cols: Vec<C>) -> Column {
+    let mut args = vec![lit(format)];
+    args.extend(columns(cols));
+    call("format_string", args)
+}
+
+// Math functions
+
+pub fn abs(col: impl Into<Column>) -> Column {
+    call("abs", vec![col.into()])
+}
+
+pub fn sqrt(col: impl Into<Column>) -> Column {
+    call("sqrt", vec![col.into()])
+}
+
+pub fn cbrt(col: impl Into<Column>) -> Column {
+    call("cbrt", vec![col.into()])
+}
+
+pub fn exp(col: impl Into<Column>) -> Column {
+    call("exp", vec![col.into()])
+}
+
+/// Natural logarithm.
+pub fn log(col: impl Into<Column>) -> Column {
+    call("ln", vec![col.into()])
+}
+
+pub fn log10(col: impl Into<Column>) -> Column {
+    call("log10", vec![col.into()])
+}
+
+pub fn log2(col: impl Into<Column>) -> Column {
+    call("log2", vec![col.into()])
+}
+
+pub fn pow(base: impl Into<Column>, exponent: impl Into<Column>) -> Column {
+    call("power", vec![base.into(), exponent.into()])
+}
+
+/// Rounds to `scale` decimal places, half away from zero.
+pub fn round(col: impl Into<Column>, scale: i32) -> Column {
+    call("round", vec![col.into(), lit(scale)])
+}
+
+/// Rounds to `scale` decimal places, half to even.
+pub fn bround(col: impl Into<Column>, scale: i32) -> Column {
+    call("bround", vec![col.into(), lit(scale)])
+}
+
+pub fn floor(col: impl Into<Column>) -> Column {
+    call("floor", vec![col.into()])
+}
+
+pub fn ceil(col: impl Into<Column>) -> Column {
+    call("ceil", vec![col.into()])
+}
+
+pub fn signum(col: impl Into<Column>) -> Column {
+    call("signum", vec![col.into()])
+}
+
+pub fn sin(col: impl Into<Column>) -> Column {
+    call("sin", vec![col.into()])
+}
+
+pub fn cos(col: impl Into<Column>) -> Column {
+    call("cos", vec![col.into()])
+}
+
+pub fn tan(col: impl Into<Column>) -> Column {
+    call("tan", vec![col.into()])
+}
+
+pub fn degrees(col: impl Into<Column>) -> Column {
+    call("degrees", vec![col.into()])
+}
+
+pub fn radians(col: impl Into<Column>) -> Column {
+    call("radians", vec![col.into()])
+}
+
+/// Greatest value, skipping nulls.
+pub fn greatest<C: Into<Column>>(cols: Vec<C>) -> Column {
+    call("greatest", columns(cols))
+}
+
+/// Least value, skipping nulls.
+pub fn least<C: Into<Column>>(cols: Vec<C>) -> Column {
+    call("least", columns(cols))
+}
+
+/// Random value uniformly distributed in [0, 1), reproducible with `seed`.
+pub fn rand(seed: Option<i64>) -> Column {
+    call("rand", seed.into_iter().map(lit).collect())
+}
+
+// Date and time functions
+
+pub fn current_date() -> Column {
+    call("current_date", vec![])
+}
+
+pub fn current_timestamp() -> Column {
+    call("current_timestamp", vec![])
+}
+
+pub fn date_add(start: impl Into<Column>, days: i32) -> Column {
+    call("date_add", vec![start.into(), lit(days)])
+}
+
+pub fn date_sub(start: impl Into<Column>, days: i32) -> Column {
+    call("date_sub", vec![start.into(), lit(days)])
+}
+
+/// Number of days from `start` to `end`.
+pub fn datediff(end: impl Into<Column>, start: impl Into<Column>) -> Column {
+    call("datediff", vec![end.into(), start.into()])
+}
+
+pub fn add_months(start: impl Into<Column>, months: i32) -> Column {
+    call("add_months", vec![start.into(), lit(months)])
+}
+
+/// Number of months from `start` to `end`, with a fractional part unless both
+/// are on the same day of the month or the last day of their month.
+pub fn months_between(end: impl Into<Column>, start: impl Into<Column>) -> Column {
+    call("months_between", vec![end.into(), start.into()])
+}
+
+pub fn year(col: impl Into<Column>) -> Column {
+    call("year", vec![col.into()])
+}
+
+pub fn month(col: impl Into<Column>) -> Column {
+    call("month", vec![col.into()])
+}
+
+pub fn dayofmonth(col: impl Into<Column>) -> Column {
+    call("dayofmonth", vec![col.into()])
+}
+
+/// Day of the week, from 1 for Sunday to 7 for Saturday.
+pub fn dayofweek(col: impl Into<Column>) -> Column {
+    call("dayofweek", vec![col.into()])
+}
+
+pub fn dayofyear(col: impl Into<Column>) -> Column {
+    call("dayofyear", vec![col.into()])
+}
+
+pub fn weekofyear(col: impl Into<Column>) -> Column {
+    call("weekofyear", vec![col.into()])
+}
+
+pub fn hour(col: impl Into<Column>) -> Column {
+    call("hour", vec![col.into()])
+}
+
+pub fn minute(col: impl Into<Column>) -> Column {
+    call("minute", vec![col.into()])
+}
+
+pub fn second(col: impl Into<Column>) -> Column {
+    call("second", vec![col.into()])
+}
+
+/// Parses a date with the datetime pattern `format`, or casts to a date when
+/// `None`.
+pub fn to_date(col: impl Into<Column>, format: Option<&str>) -> Column {
+    let mut args = vec![col.into()];
+    args.extend(format.map(lit));
+    call("to_date", args)
+}
+
+/// Parses a timestamp with the datetime pattern `format`, or casts to a
+/// timestamp when `None`.
+pub fn to_timestamp(col: impl Into<Column>, format: Option<&str>) -> Column {
+    let mut args = vec![col.into()];
+    args.extend(format.map(lit));
+    call("to_timestamp", args)
+}
+
+/// Formats a date or timestamp with the datetime pattern `format`.
+pub fn date_format(col: impl Into<Column>, format: &str) -> Column {
+    call("date_format", vec![col.into(), lit(format)])
+}
+
+/// Truncates a timestamp to the `unit`, e.g. `year`, `month`, `day` or
+/// `hour`.
+pub fn date_trunc(unit: &str, col: impl Into<Column>) -> Column {
+    call("date_trunc", vec![lit(unit), col.into()])
+}
+
+/// Formats the seconds since the epoch with the datetime pattern `format`.
+pub fn from_unixtime(col: impl Into<Column>, format: &str) -> Column {
+    call("from_unixtime", vec![col.into(), lit(format)])
+}
+
+/// Seconds since the epoch of a date, timestamp or string parsed with the
+/// datetime pattern `format`.
+pub fn unix_timestamp(col: impl Into<Column>, format: &str) -> Column {
+    call("unix_timestamp", vec![col.into(), lit(format)])
+}
+
+// Collection functions
+
+pub fn array<C: Into<Column>>(cols: Vec<C>) -> Column {
+    call("array", columns(cols))
+}
+
+/// Map from alternating keys and values.
+pub fn create_map<C: Into<Column>>(cols: Vec<C>) -> Column {
+    call("map", columns(cols))
+}
+
+pub fn map_from_arrays(keys: impl Into<Column>, values: impl Into<Column>) -> Column {
+    call("map_from_arrays", vec![keys.into(), values.into()])
+}
+
+pub fn map_keys(col: impl Into<Column>) -> Column {
+    call("map_keys", vec![col.into()])
+}
+
+pub fn map_values(col: impl Into<Column>) -> Column {
+    call("map_values", vec![col.into()])
+}
+
+pub fn array_contains(col: impl Into<Column>, value: impl Into<Column>) -> Column {
+    call("array_contains", vec![col.into(), value.into()])
+}
+
+/// Position of the first occurrence of `value`, counted from 1, or 0 when
+/// not found.
+pub fn array_position(col: impl Into<Column>, value: impl Into<Column>) -> Column {
+    call("array_position", vec![col.into(), value.into()])
+}
+
+pub fn array_distinct(col: impl Into<Column>) -> Column {
+    call("array_distinct", vec![col.into()])
+}
+
+pub fn array_union(col1: impl Into<Column>, col2: impl Into<Column>) -> Column {
+    call("array_union", vec![col1.into(), col2.into()])
+}
+
+pub fn array_intersect(col1: impl Into<Column>, col2: impl Into<Column>) -> Column {
+    call("array_intersect", vec![col1.into(), col2.into()])
+}
+
+pub fn array_except(col1: impl Into<Column>, col2: impl Into<Column>) -> Column {
+    call("array_except", vec![col1.into(), col2.into()])
+}
+
+/// Concatenates the elements of a string array with `delimiter`.
+pub fn array_join(col: impl Into<Column>, delimiter: &str) -> Column {
+    call("array_join", vec![col.into(), lit(delimiter)])
+}
+
+/// Number of elements of an array or a map.
+pub fn size(col: impl Into<Column>) -> Column {
+    call("size", vec![col.into()])
+}
+
+/// Element of an array at `extraction`, counted from 1, or value of a map
+/// for the key `extraction`.
+pub fn element_at(col: impl Into<Column>, extraction: impl Into<Column>) -> Column {
+    call("element_at", vec![col.into(), extraction.into()])
+}
+
+/// `length` elements of an array from `start`, counted from 1.
+pub fn slice(col: impl Into<Column>, start: i32, length: i32) -> Column {
+    call("slice", vec![col.into(), lit(start), lit(length)])
+}
+
+/// Sorts an array, with nulls first in ascending order and last in
+/// descending order.
+pub fn sort_array(col: impl Into<Column>, asc: bool) -> Column {
+    call("sort_array", vec![col.into(), lit(asc)])
+}
+
+/// Flattens an array of arrays by one level.
+pub fn flatten(col: impl Into<Column>) -> Column {
+    call("flatten", vec![col.into()])
+}
+
+/// Row per element of an array, or per entry of a map, dropping the rows
+/// where it is null or empty.
+pub fn explode(col: impl Into<Column>) -> Column {
+    call("explode", vec![col.into()])
+}
+
+/// Like `explode`, but keeps a row with null when the array or map is null
+/// or empty.
+pub fn explode_outer(col: impl Into<Column>) -> Column {
+    call("explode_outer", vec![col.into()])
+}
+
+/// Like `explode`, with the position of each element.
+pub fn posexplode(col: impl Into<Column>) -> Column {
+    call("posexplode", vec![col.into()])
+}
+
+// Higher-order functions
+
+thread_local! {
+    static LAMBDA_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Nesting level of the lambda being built, so that the variables of nested
+/// lambdas do not shadow each other.
+struct LambdaScope(usize);
+
+impl LambdaScope {
+    fn enter() -> Self {
+        LambdaScope(LAMBDA_DEPTH.with(|depth| {
+            depth.set(depth.get() + 1);
+            depth.get()
+        }))
+    }
+}
+
+impl Drop for LambdaScope {
+    fn drop(&mut self) {
+        LAMBDA_DEPTH.with(|depth| depth.set(self.0 - 1));
+    }
+}
+
+/// Builds the lambda function whose `N` variables are passed to `f`.
+fn lambda<const N: usize>(f: impl FnOnce([Column; N]) -> Column) -> Column {
+    const NAMES: [&str; 3] = ["x", "y", "z"];
+    let scope = LambdaScope::enter();
+    let variables: [spark::expression::UnresolvedNamedLambdaVariable; N] =
+        std::array::from_fn(|i| spark::expression::UnresolvedNamedLambdaVariable {
+            name_parts: vec![format!("{}_{}", NAMES[i], scope.0)],
+        });
+    let body = f(variables
+        .clone()
+        .map(|variable| Column::from(ExprType::UnresolvedNamedLambdaVariable(variable))));
+    Column::from(ExprType::LambdaFunction(Box::new(
+        spark::expression::LambdaFunction {
+            function: Some(Box::new(body.expression)),
+            arguments: variables.to_vec(),
+        },
+    )))
+}
+
+/// Applies `f` to each element of an array.
+pub fn transform(col: impl Into<Column>, f: impl FnOnce(Column) -> Column) -> Column {
+    call("transform", vec![col.into(), lambda(|[x]| f(x))])
+}
+
+/// Elements of an array for which `f` holds.
+pub fn filter(col: impl Into<Column>, f: impl FnOnce(Column) -> Column) -> Column {
+    call("filter", vec![col.into(), lambda(|[x]| f(x))])
+}
+
+/// Whether `f` holds for at least one element of an array.
+pub fn exists(col: impl Into<Column>, f: impl FnOnce(Column) -> Column) -> Column {
+    call("exists", vec![col.into(), lambda(|[x]| f(x))])
+}
+
+/// Whether `f` holds for all the elements of an array.
+pub fn forall(col: impl Into<Column>, f: impl FnOnce(Column) -> Column) -> Column {
+    call("forall", vec![col.into(), lambda(|[x]| f(x))])
+}
+
+/// Folds the elements of an array into a single value, starting from
+/// `initial_value`, with `merge` called with the accumulator and an element.
+pub fn aggregate(
+    col: impl Into<Column>,
+    initial_value: impl Into<Column>,
+    merge: impl FnOnce(Column, Column) -> Column,
+) -> Column {
+    call(
+        "aggregate",
+        vec![
+            col.into(),
+            initial_value.into(),
+            lambda(|[acc, x]| merge(acc, x)),
+        ],
+    )
+}
+
+/// Like `aggregate`, with `finish` applied to the final accumulator.
+pub fn aggregate_with_finish(
+    col: impl Into<Column>,
+    initial_value: impl Into<Column>,
+    merge: impl FnOnce(Column, Column) -> Column,
+    finish: impl FnOnce(Column) -> Column,
+) -> Column {
+    call(
+        "aggregate",
+        vec![
+            col.into(),
+            initial_value.into(),
+            lambda(|[acc, x]| merge(acc, x)),
+            lambda(|[acc]| finish(acc)),
+        ],
+    )
+}
+
+/// Merges two arrays element-wise with `f`, the shorter array being padded
+/// with nulls.
+pub fn zip_with(
+    left: impl Into<Column>,
+    right: impl Into<Column>,
+    f: impl FnOnce(Column, Column) -> Column,
+) -> Column {
+    call(
+        "zip_with",
+        vec![left.into(), right.into(), lambda(|[x, y]| f(x, y))],
+    )
+}
+
+/// Entries of a map for which `f`, called with the key and the value, holds.
+pub fn map_filter(col: impl Into<Column>, f: impl FnOnce(Column, Column) -> Column) -> Column {
+    call("map_filter", vec![col.into(), lambda(|[k, v]| f(k, v))])
+}
+
+/// Replaces the keys of a map by `f`, called with the key and the value.
+pub fn transform_keys(col: impl Into<Column>, f: impl FnOnce(Column, Column) -> Column) -> Column {
+    call("transform_keys", vec![col.into(), lambda(|[k, v]| f(k, v))])
+}
+
+/// Replaces the values of a map by `f`, called with the key and the value.
+pub fn transform_values(
+    col: impl Into<Column>,
+    f: impl FnOnce(Column, Column) -> Column,
+) -> Column {
+    call(
+        "transform_values",
+        vec![col.into(), lambda(|[k, v]| f(k, v))],
+    )
+}
+
+// JSON functions
+
+/// Parses a JSON string with `schema`, in DDL format, e.g.
+/// `a INT, b STRING`, and the options of the JSON data source.
+pub fn from_json(col: impl Into<Column>, schema: &str, options: HashMap<String, String>) -> Column {
+    let mut args = vec![col.into(), lit(schema)];
+    if !options.is_empty() {
+        args.push(options_map(options));
+    }
+    call("from_json", args)
+}
+
+/// Converts a struct, array or map into a JSON string.
+pub fn to_json(col: impl Into<Column>) -> Column {
+    call("to_json", vec![col.into()])
+}
+
+/// Extracts the JSON object at `path`, e.g. `$.address.city`, as a string.
+pub fn get_json_object(col: impl Into<Column>, path: &str) -> Column {
+    call("get_json_object", vec![col.into(), lit(path)])
+}
+
+/// Extracts the top-level `fields` of a JSON string, as columns `c0`, `c1`,
+/// and so on.
+pub fn json_tuple(col: impl Into<Column>, fields: Vec<&str>) -> Column {
+    let mut args = vec![col.into()];
+    args.extend(fields.into_iter().map(lit));
+    call("json_tuple", args)
+}
+
+/// Schema of the JSON string `json`, in DDL format.
+pub fn schema_of_json(json: &str) -> Column {
+    call("schema_of_json", vec![lit(json)])
+}
+
+pub fn json_array_length(col: impl Into<Column>) -> Column {
+    call("json_array_length", vec![col.into()])
+}
+
+fn options_map(options: HashMap<String, String>) -> Column {
+    create_map(
+        options
+            .into_iter()
+            .flat_map(|(key, value)| [lit(key), lit(value)])
+            .collect(),
+    )
+}
+
+// Aggregate functions
+
+/// Number of rows where `col` is not null, e.g. `count(Literal::from(1))`
+/// counts all the rows.
+pub fn count(col: impl Into<Column>) -> Column {
+    call("count", vec![col.into()])
+}
+
+/// Number of distinct combinations of values of `cols`, none of them null.
+pub fn count_distinct<C: Into<Column>>(cols: Vec<C>) -> Column {
+    call_distinct("count", columns(cols))
+}
+
+/// Estimated number of distinct values, within the relative standard
+/// deviation `rsd`, 0.05 by default.
+pub fn approx_count_distinct(col: impl Into<Column>, rsd: Option<f64>) -> Column {
+    let mut args = vec![col.into()];
+    args.extend(rsd.map(lit));
+    call("approx_count_distinct", args)
+}
+
+pub fn sum(col: impl Into<Column>) -> Column {
+    call("sum", vec![col.into()])
+}
+
+pub fn sum_distinct(col: impl Into<Column>) -> Column {
+    call_distinct("sum", vec![col.into()])
+}
+
+pub fn avg(col: impl Into<Column>) -> Column {
+    call("avg", vec![col.into()])
+}
+
+pub fn min(col: impl Into<Column>) -> Column {
+    call("min", vec![col.into()])
+}
+
+pub fn max(col: impl Into<Column>) -> Column {
+    call("max", vec![col.into()])
+}
+
+/// Value of `col` in the row with the minimum value of `ord`.
+pub fn min_by(col: impl Into<Column>, ord: impl Into<Column>) -> Column {
+    call("min_by", vec![col.into(), ord.into()])
+}
+
+/// Value of `col` in the row with the maximum value of `ord`.
+pub fn max_by(col: impl Into<Column>, ord: impl Into<Column>) -> Column {
+    call("max_by", vec![col.into(), ord.into()])
+}
+
+/// First value of a group, the first non-null one when `ignore_nulls`. The
+/// result depends on the order of the rows, which is not deterministic after
+/// a shuffle.
+pub fn first(col: impl Into<Column>, ignore_nulls: bool) -> Column {
+    call("first", vec![col.into(), lit(ignore_nulls)])
+}
+
+/// Last value of a group, the last non-null one when `ignore_nulls`. The
+/// result depends on the order of the rows, which is not deterministic after
+/// a shuffle.
+pub fn last(col: impl Into<Column>, ignore_nulls: bool) -> Column {
+    call("last", vec![col.into(), lit(ignore_nulls)])
+}
+
+pub fn collect_list(col: impl Into<Column>) -> Column {
+    call("collect_list", vec![col.into()])
+}
+
+pub fn collect_set(col: impl Into<Column>) -> Column {
+    call("collect_set", vec![col.into()])
+}
+
+/// Sample standard deviation.
+pub fn stddev(col: impl Into<Column>) -> Column {
+    call("stddev", vec![col.into()])
+}
+
+/// Sample variance.
+pub fn variance(col: impl Into<Column>) -> Column {
+    call("variance", vec![col.into()])
+}
+
+pub fn median(col: impl Into<Column>) -> Column {
+    call("median", vec![col.into()])
+}
+
+/// Pearson correlation coefficient.
+pub fn corr(col1: impl Into<Column>, col2: impl Into<Column>) -> Column {
+    call("corr", vec![col1.into(), col2.into()])
+}
+
+// Conditional functions
+
+/// Starts a `CASE WHEN` expression evaluating to `value` when `condition`
+/// holds, continued with `When::when` and `When::otherwise`.
+pub fn when(condition: impl Into<Column>, value: impl Into<Column>) -> When {
+    When {
+        args: vec![condition.into(), value.into()],
+    }
+}
+
+/// `CASE WHEN` expression, null when none of the conditions hold unless
+/// completed with `otherwise`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct When {
+    args: Vec<Column>,
+}
+
+impl When {
+    pub fn when(mut self, condition: impl Into<Column>, value: impl Into<Column>) -> When {
+        self.args.push(condition.into());
+        self.args.push(value.into());
+        self
+    }
+
+    pub fn otherwise(mut self, value: impl Into<Column>) -> Column {
+        self.args.push(value.into());
+        call("when", self.args)
+    }
+}
+
+impl From<When> for Column {
+    fn from(when: When) -> Self {
+        call("when", when.args)
+    }
+}
+
+/// First non-null value.
+pub fn coalesce<C: Into<Column>>(cols: Vec<C>) -> Column {
+    call("coalesce", columns(cols))
+}
+
+pub fn isnull(col: impl Into<Column>) -> Column {
+    call("isnull", vec![col.into()])
+}
+
+pub fn isnan(col: impl Into<Column>) -> Column {
+    call("isnan", vec![col.into()])
+}
+
+/// Null when `col1` equals `col2`, `col1` otherwise.
+pub fn nullif(col1: impl Into<Column>, col2: impl Into<Column>) -> Column {
+    call("nullif", vec![col1.into(), col2.into()])
+}
+
+// Window functions
+
+/// Sequential number of the row in its window partition, starting at 1.
+pub fn row_number() -> Column {
+    call("row_number", vec![])
+}
+
+/// Rank of the row in its window partition, with gaps after ties.
+pub fn rank() -> Column {
+    call("rank", vec![])
+}
+
+/// Rank of the row in its window partition, without gaps after ties.
+pub fn dense_rank() -> Column {
+    call("dense_rank", vec![])
+}
+
+/// Relative rank of the row in its window partition, between 0 and 1.
+pub fn percent_rank() -> Column {
+    call("percent_rank", vec![])
+}
+
+/// Fraction of the rows of the window partition up to the row, ties
+/// included.
+pub fn cume_dist() -> Column {
+    call("cume_dist", vec![])
+}
+
+/// Number of the bucket of the row, when the window partition is divided
+/// into `n` buckets of rows.
+pub fn ntile(n: i32) -> Column {
+    call("ntile", vec![lit(n)])
+}
+
+/// Value of `col` `offset` rows before the row, or `default` when there is
+/// no such row, null when `None`.
+pub fn lag(col: impl Into<Column>, offset: i32, default: Option<Column>) -> Column {
+    call("lag", offset_args(col.into(), offset, default))
+}
+
+/// Value of `col` `offset` rows after the row, or `default` when there is no
+/// such row, null when `None`.
+pub fn lead(col: impl Into<Column>, offset: i32, default: Option<Column>) -> Column {
+    call("lead", offset_args(col.into(), offset, default))
+}
+
+fn offset_args(col: Column, offset: i32, default: Option<Column>) -> Vec<Column> {
+    let mut args = vec![col, lit(offset)];
+    args.extend(default);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::col;
+
+    fn function(column: Column) -> spark::expression::UnresolvedFunction {
+        match column.expression.expr_type {
+            Some(ExprType::UnresolvedFunction(function)) => function,
+            other => panic!("Expected a function, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_when_alternates_conditions_and_values() {
+        let case = function(
+            when(col("a"), Literal::from(1))
+                .when(col("b"), Literal::from(2))
+                .otherwise(Literal::from(3)),
+        );
+        assert_eq!(case.function_name, "when");
+        assert_eq!(
+            case.arguments,
+            vec![
+                col("a").expression,
+                lit(1).expression,
+                col("b").expression,
+                lit(2).expression,
+                lit(3).expression,
+            ]
+        );
+        assert_eq!(function(when(col("a"), col("b")).into()).arguments.len(), 2);
+    }
+
+    fn lambda_function(column: Column) -> spark::expression::LambdaFunction {
+        match column.expression.expr_type {
+            Some(ExprType::LambdaFunction(lambda)) => *lambda,
+            other => panic!("Expected a lambda function, got {:?}", other),
+        }
+    }
+
+    fn variable_names(lambda: &spark::expression::LambdaFunction) -> Vec<String> {
+        lambda
+            .arguments
+            .iter()
+            .flat_map(|variable| variable.name_parts.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_nested_lambdas_have_distinct_variables() {
+        let mut transform = function(transform("matrix", |row| transform(row, upper)));
+        let outer = lambda_function(transform.arguments.remove(1).into());
+        assert_eq!(variable_names(&outer), vec!["x_1"]);
+        let mut inner_transform = function(Column::from(*outer.function.unwrap()));
+        assert_eq!(
+            inner_transform.arguments[0],
+            Column::from(ExprType::UnresolvedNamedLambdaVariable(
+                outer.arguments[0].clone()
+            ))
+            .expression
+        );
+        let inner = lambda_function(inner_transform.arguments.remove(1).into());
+        assert_eq!(variable_names(&inner), vec!["x_2"]);
+        let sibling = function(zip_with("a", "b", |x, y| array(vec![x, y])));
+        assert_eq!(
+            variable_names(&lambda_function(sibling.arguments[2].clone().into())),
+            vec!["x_1", "y_1"]
+        );
+    }
+
+    #[test]
+    fn test_count_distinct_sets_the_distinct_flag() {
+        let count = function(count_distinct(vec!["a", "b"]));
+        assert_eq!(count.function_name, "count");
+        assert!(count.is_distinct);
+        assert_eq!(count.arguments.len(), 2);
+    }
+}
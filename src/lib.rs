@@ -1,11 +1,23 @@
+#[allow(clippy::large_enum_variant)]
 pub mod spark {
     tonic::include_proto!("spark.connect");
 }
 
 mod arrow;
+mod artifact;
+pub mod catalog;
+pub mod column;
 pub mod dataframe;
+pub mod de;
 pub mod error;
+pub mod functions;
+pub mod literal;
+pub mod metrics;
+pub mod options;
 mod plan;
 mod session;
+pub mod types;
+pub mod udf;
+pub mod window;
 
 pub use session::RemoteSparkSession;
@@ -0,0 +1,591 @@
+//! Conversions of Rust values into Spark literals, passed to expressions with
+//! `functions::lit`.
+//!
+//! Arrays and maps need the Spark type of their elements even when empty, so
+//! their elements implement `SparkDataType`. `Option::None` converts into a
+//! null of the type of `T`, other nulls are created with `null`.
+//!
+//! Unsigned integers convert into the next wider signed Spark type, `u64` into
+//! `DECIMAL(20, 0)`. `u8` has no conversion, `Vec<u8>` being binary.
+use crate::error::{InvalidArgumentError, SparkError};
+use crate::spark;
+use crate::spark::data_type::Kind;
+use crate::spark::expression::literal::{self, LiteralType};
+use crate::spark::expression::Literal;
+use crate::types;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+const MAX_DECIMAL_PRECISION: u8 = 38;
+/// Digits of `u64::MAX`.
+const U64_DECIMAL_PRECISION: u8 = 20;
+
+/// Rust type with a fixed Spark data type.
+pub trait SparkDataType {
+    fn data_type() -> spark::DataType;
+}
+
+fn data_type(kind: Kind) -> spark::DataType {
+    spark::DataType { kind: Some(kind) }
+}
+
+impl SparkDataType for bool {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Boolean(spark::data_type::Boolean::default()))
+    }
+}
+
+impl SparkDataType for i8 {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Byte(spark::data_type::Byte::default()))
+    }
+}
+
+impl SparkDataType for i16 {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Short(spark::data_type::Short::default()))
+    }
+}
+
+impl SparkDataType for i32 {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Integer(spark::data_type::Integer::default()))
+    }
+}
+
+impl SparkDataType for i64 {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Long(spark::data_type::Long::default()))
+    }
+}
+
+impl SparkDataType for u16 {
+    fn data_type() -> spark::DataType {
+        i32::data_type()
+    }
+}
+
+impl SparkDataType for u32 {
+    fn data_type() -> spark::DataType {
+        i64::data_type()
+    }
+}
+
+impl SparkDataType for u64 {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Decimal(spark::data_type::Decimal {
+            precision: Some(U64_DECIMAL_PRECISION.into()),
+            scale: Some(0),
+            type_variation_reference: 0,
+        }))
+    }
+}
+
+impl SparkDataType for f32 {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Float(spark::data_type::Float::default()))
+    }
+}
+
+impl SparkDataType for f64 {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Double(spark::data_type::Double::default()))
+    }
+}
+
+impl SparkDataType for String {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::String(spark::data_type::String::default()))
+    }
+}
+
+impl SparkDataType for &str {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::String(spark::data_type::String::default()))
+    }
+}
+
+impl SparkDataType for Vec<u8> {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Binary(spark::data_type::Binary::default()))
+    }
+}
+
+impl SparkDataType for NaiveDate {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Date(spark::data_type::Date::default()))
+    }
+}
+
+impl SparkDataType for NaiveDateTime {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::TimestampNtz(spark::data_type::TimestampNtz::default()))
+    }
+}
+
+impl<Tz: TimeZone> SparkDataType for DateTime<Tz> {
+    fn data_type() -> spark::DataType {
+        data_type(Kind::Timestamp(spark::data_type::Timestamp::default()))
+    }
+}
+
+impl SparkDataType for chrono::Duration {
+    fn data_type() -> spark::DataType {
+        day_time_interval_type()
+    }
+}
+
+impl SparkDataType for Duration {
+    fn data_type() -> spark::DataType {
+        day_time_interval_type()
+    }
+}
+
+fn day_time_interval_type() -> spark::DataType {
+    data_type(Kind::DayTimeInterval(spark::data_type::DayTimeInterval {
+        start_field: Some(types::DAY),
+        end_field: Some(types::SECOND),
+        type_variation_reference: 0,
+    }))
+}
+
+impl<T: SparkDataType> SparkDataType for Vec<T> {
+    fn data_type() -> spark::DataType {
+        array_type(T::data_type())
+    }
+}
+
+impl<K: SparkDataType, V: SparkDataType> SparkDataType for HashMap<K, V> {
+    fn data_type() -> spark::DataType {
+        map_type(K::data_type(), V::data_type())
+    }
+}
+
+impl<T: SparkDataType> SparkDataType for Option<T> {
+    fn data_type() -> spark::DataType {
+        T::data_type()
+    }
+}
+
+fn array_type(element_type: spark::DataType) -> spark::DataType {
+    data_type(Kind::Array(Box::new(spark::data_type::Array {
+        element_type: Some(Box::new(element_type)),
+        contains_null: true,
+        type_variation_reference: 0,
+    })))
+}
+
+fn map_type(key_type: spark::DataType, value_type: spark::DataType) -> spark::DataType {
+    data_type(Kind::Map(Box::new(spark::data_type::Map {
+        key_type: Some(Box::new(key_type)),
+        value_type: Some(Box::new(value_type)),
+        value_contains_null: true,
+        type_variation_reference: 0,
+    })))
+}
+
+/// Decimal number `unscaled * 10^-scale`, with at most `precision` digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decimal {
+    unscaled: i128,
+    precision: u8,
+    scale: u8,
+}
+
+impl Decimal {
+    /// Fails unless `precision` is between 1 and 38, `scale` is at most
+    /// `precision` and `unscaled` has at most `precision` digits.
+    pub fn new(unscaled: i128, precision: u8, scale: u8) -> Result<Decimal, SparkError> {
+        if precision == 0 || precision > MAX_DECIMAL_PRECISION || scale > precision {
+            return Err(SparkError::InvalidArgument(InvalidArgumentError(format!(
+                "Invalid decimal precision {} and scale {}",
+                precision, scale
+            ))));
+        }
+        if unscaled.unsigned_abs().to_string().len() > precision as usize {
+            return Err(SparkError::InvalidArgument(InvalidArgumentError(format!(
+                "{} does not fit in precision {}",
+                unscaled, precision
+            ))));
+        }
+        Ok(Decimal {
+            unscaled,
+            precision,
+            scale,
+        })
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!(
+            "{:0>width$}",
+            self.unscaled.unsigned_abs(),
+            width = self.scale as usize + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        let sign = if self.unscaled < 0 { "-" } else { "" };
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, integer)
+        } else {
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
+
+/// Null of type `data_type`.
+pub fn null(data_type: spark::DataType) -> Literal {
+    Literal::from(LiteralType::Null(data_type))
+}
+
+/// Struct whose fields are named and valued by `fields`, in order.
+pub fn struct_literal(fields: Vec<(&str, Literal)>) -> Literal {
+    let struct_fields = fields
+        .iter()
+        .map(|(name, value)| spark::data_type::StructField {
+            name: name.to_string(),
+            data_type: Some(data_type_of(value)),
+            nullable: true,
+            metadata: None,
+        })
+        .collect();
+    Literal::from(LiteralType::Struct(literal::Struct {
+        struct_type: Some(data_type(Kind::Struct(spark::data_type::Struct {
+            fields: struct_fields,
+            type_variation_reference: 0,
+        }))),
+        elements: fields.into_iter().map(|(_, value)| value).collect(),
+    }))
+}
+
+/// Spark data type of `literal`, which literals always carry.
+fn data_type_of(literal: &Literal) -> spark::DataType {
+    let kind = match &literal.literal_type {
+        Some(LiteralType::Null(data_type)) => return data_type.clone(),
+        Some(LiteralType::Binary(_)) => return Vec::<u8>::data_type(),
+        Some(LiteralType::Boolean(_)) => return bool::data_type(),
+        Some(LiteralType::Byte(_)) => return i8::data_type(),
+        Some(LiteralType::Short(_)) => return i16::data_type(),
+        Some(LiteralType::Integer(_)) => return i32::data_type(),
+        Some(LiteralType::Long(_)) => return i64::data_type(),
+        Some(LiteralType::Float(_)) => return f32::data_type(),
+        Some(LiteralType::Double(_)) => return f64::data_type(),
+        Some(LiteralType::String(_)) => return String::data_type(),
+        Some(LiteralType::Date(_)) => return NaiveDate::data_type(),
+        Some(LiteralType::Timestamp(_)) => return DateTime::<Utc>::data_type(),
+        Some(LiteralType::TimestampNtz(_)) => return NaiveDateTime::data_type(),
+        Some(LiteralType::DayTimeInterval(_)) => return day_time_interval_type(),
+        Some(LiteralType::Decimal(decimal)) => Kind::Decimal(spark::data_type::Decimal {
+            precision: decimal.precision,
+            scale: decimal.scale,
+            type_variation_reference: 0,
+        }),
+        Some(LiteralType::CalendarInterval(_)) => {
+            Kind::CalendarInterval(spark::data_type::CalendarInterval::default())
+        }
+        Some(LiteralType::YearMonthInterval(_)) => {
+            Kind::YearMonthInterval(spark::data_type::YearMonthInterval::default())
+        }
+        Some(LiteralType::Array(array)) => {
+            return array_type(array.element_type.clone().unwrap_or_default())
+        }
+        Some(LiteralType::Map(map)) => {
+            return map_type(
+                map.key_type.clone().unwrap_or_default(),
+                map.value_type.clone().unwrap_or_default(),
+            )
+        }
+        Some(LiteralType::Struct(st)) => return st.struct_type.clone().unwrap_or_default(),
+        None => Kind::Null(spark::data_type::Null::default()),
+    };
+    data_type(kind)
+}
+
+impl From<LiteralType> for Literal {
+    fn from(literal_type: LiteralType) -> Self {
+        Literal {
+            literal_type: Some(literal_type),
+        }
+    }
+}
+
+impl From<bool> for Literal {
+    fn from(value: bool) -> Self {
+        Literal::from(LiteralType::Boolean(value))
+    }
+}
+
+impl From<i8> for Literal {
+    fn from(value: i8) -> Self {
+        Literal::from(LiteralType::Byte(value.into()))
+    }
+}
+
+impl From<i16> for Literal {
+    fn from(value: i16) -> Self {
+        Literal::from(LiteralType::Short(value.into()))
+    }
+}
+
+impl From<i32> for Literal {
+    fn from(value: i32) -> Self {
+        Literal::from(LiteralType::Integer(value))
+    }
+}
+
+impl From<i64> for Literal {
+    fn from(value: i64) -> Self {
+        Literal::from(LiteralType::Long(value))
+    }
+}
+
+impl From<u16> for Literal {
+    fn from(value: u16) -> Self {
+        Literal::from(LiteralType::Integer(value.into()))
+    }
+}
+
+impl From<u32> for Literal {
+    fn from(value: u32) -> Self {
+        Literal::from(LiteralType::Long(value.into()))
+    }
+}
+
+impl From<u64> for Literal {
+    fn from(value: u64) -> Self {
+        Literal::from(Decimal {
+            unscaled: value.into(),
+            precision: U64_DECIMAL_PRECISION,
+            scale: 0,
+        })
+    }
+}
+
+impl From<f32> for Literal {
+    fn from(value: f32) -> Self {
+        Literal::from(LiteralType::Float(value))
+    }
+}
+
+impl From<f64> for Literal {
+    fn from(value: f64) -> Self {
+        Literal::from(LiteralType::Double(value))
+    }
+}
+
+impl From<&str> for Literal {
+    fn from(value: &str) -> Self {
+        Literal::from(LiteralType::String(value.to_string()))
+    }
+}
+
+impl From<String> for Literal {
+    fn from(value: String) -> Self {
+        Literal::from(LiteralType::String(value))
+    }
+}
+
+impl From<Vec<u8>> for Literal {
+    fn from(value: Vec<u8>) -> Self {
+        Literal::from(LiteralType::Binary(value))
+    }
+}
+
+impl From<Decimal> for Literal {
+    fn from(value: Decimal) -> Self {
+        Literal::from(LiteralType::Decimal(literal::Decimal {
+            value: value.to_string(),
+            precision: Some(value.precision.into()),
+            scale: Some(value.scale.into()),
+        }))
+    }
+}
+
+/// Date, as the number of days since the epoch.
+impl From<NaiveDate> for Literal {
+    fn from(value: NaiveDate) -> Self {
+        // The default date is the epoch
+        let days = value.signed_duration_since(NaiveDate::default()).num_days();
+        Literal::from(LiteralType::Date(days as i32))
+    }
+}
+
+/// Timestamp without time zone, as the number of microseconds from the epoch
+/// to the wall-clock value, with no time zone conversion.
+impl From<NaiveDateTime> for Literal {
+    fn from(value: NaiveDateTime) -> Self {
+        Literal::from(LiteralType::TimestampNtz(
+            Utc.from_utc_datetime(&value).timestamp_micros(),
+        ))
+    }
+}
+
+/// Timestamp, as the number of microseconds since the epoch in UTC.
+impl<Tz: TimeZone> From<DateTime<Tz>> for Literal {
+    fn from(value: DateTime<Tz>) -> Self {
+        Literal::from(LiteralType::Timestamp(value.timestamp_micros()))
+    }
+}
+
+/// Day-time interval, saturated to the range of microseconds of Spark.
+impl From<chrono::Duration> for Literal {
+    fn from(value: chrono::Duration) -> Self {
+        let micros = value
+            .num_microseconds()
+            .unwrap_or(if value < chrono::Duration::zero() {
+                i64::MIN
+            } else {
+                i64::MAX
+            });
+        Literal::from(LiteralType::DayTimeInterval(micros))
+    }
+}
+
+/// Day-time interval, saturated to the range of microseconds of Spark.
+impl From<Duration> for Literal {
+    fn from(value: Duration) -> Self {
+        let micros = i64::try_from(value.as_micros()).unwrap_or(i64::MAX);
+        Literal::from(LiteralType::DayTimeInterval(micros))
+    }
+}
+
+impl<T: Into<Literal> + SparkDataType> From<Vec<T>> for Literal {
+    fn from(value: Vec<T>) -> Self {
+        Literal::from(LiteralType::Array(literal::Array {
+            element_type: Some(T::data_type()),
+            elements: value.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+impl<K, V> From<HashMap<K, V>> for Literal
+where
+    K: Into<Literal> + SparkDataType,
+    V: Into<Literal> + SparkDataType,
+{
+    fn from(value: HashMap<K, V>) -> Self {
+        let (keys, values) = value
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .unzip();
+        Literal::from(LiteralType::Map(literal::Map {
+            key_type: Some(K::data_type()),
+            value_type: Some(V::data_type()),
+            keys,
+            values,
+        }))
+    }
+}
+
+impl<T: Into<Literal> + SparkDataType> From<Option<T>> for Literal {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => null(T::data_type()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimals_keep_their_scale() {
+        let decimal = |unscaled, precision, scale| {
+            Decimal::new(unscaled, precision, scale)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(decimal(12345, 5, 2), "123.45");
+        assert_eq!(decimal(-5, 3, 3), "-0.005");
+        assert_eq!(decimal(42, 2, 0), "42");
+        assert!(Decimal::new(12345, 4, 2).is_err());
+        assert!(Decimal::new(1, 39, 0).is_err());
+        assert!(Decimal::new(1, 2, 3).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_integers_widen() {
+        assert_eq!(
+            Literal::from(u16::MAX),
+            Literal::from(LiteralType::Integer(65_535))
+        );
+        assert_eq!(
+            Literal::from(u32::MAX),
+            Literal::from(LiteralType::Long(4_294_967_295))
+        );
+        let max = Literal::from(u64::MAX);
+        assert_eq!(
+            max,
+            Literal::from(LiteralType::Decimal(literal::Decimal {
+                value: "18446744073709551615".to_string(),
+                precision: Some(20),
+                scale: Some(0),
+            }))
+        );
+        assert_eq!(data_type_of(&max), u64::data_type());
+    }
+
+    #[test]
+    fn test_dates_and_times_count_from_the_epoch() {
+        let date = NaiveDate::from_ymd_opt(1970, 1, 11).unwrap();
+        assert_eq!(Literal::from(date), Literal::from(LiteralType::Date(10)));
+        let timestamp = Utc.timestamp_opt(1, 500_000_000).unwrap();
+        assert_eq!(
+            Literal::from(timestamp),
+            Literal::from(LiteralType::Timestamp(1_500_000))
+        );
+        assert_eq!(
+            Literal::from(timestamp.naive_utc()),
+            Literal::from(LiteralType::TimestampNtz(1_500_000))
+        );
+        assert_eq!(
+            Literal::from(Duration::from_millis(3)),
+            Literal::from(LiteralType::DayTimeInterval(3_000))
+        );
+    }
+
+    #[test]
+    fn test_collections_carry_the_types_of_their_elements() {
+        let empty: Vec<Option<i64>> = vec![];
+        assert_eq!(
+            data_type_of(&Literal::from(empty)),
+            array_type(i64::data_type())
+        );
+        let map: HashMap<String, Vec<f64>> = HashMap::new();
+        assert_eq!(
+            data_type_of(&Literal::from(map)),
+            map_type(String::data_type(), array_type(f64::data_type()))
+        );
+        assert_eq!(Literal::from(None::<bool>), null(bool::data_type()));
+        let point = struct_literal(vec![("x", 1.into()), ("label", None::<&str>.into())]);
+        match data_type_of(&point).kind {
+            Some(Kind::Struct(st)) => {
+                let fields: Vec<(String, Option<spark::DataType>)> = st
+                    .fields
+                    .into_iter()
+                    .map(|field| (field.name, field.data_type))
+                    .collect();
+                assert_eq!(
+                    fields,
+                    vec![
+                        ("x".to_string(), Some(i32::data_type())),
+                        ("label".to_string(), Some(String::data_type())),
+                    ]
+                );
+            }
+            other => panic!("Expected a struct type, got {:?}", other),
+        }
+    }
+}
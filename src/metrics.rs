@@ -0,0 +1,239 @@
+use crate::spark::execute_plan_response::Metrics;
+use crate::spark::expression::Literal;
+use arrow::record_batch::RecordBatch;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+const OUTPUT_ROWS_METRIC: &str = "numOutputRows";
+
+/// Result of an action, carrying the record batches together with the
+/// execution metrics reported by the server.
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+    pub batches: Vec<RecordBatch>,
+    pub metrics: QueryMetrics,
+}
+
+/// Value of a single SQL metric, as reported by a physical operator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricValue {
+    pub name: String,
+    pub value: i64,
+    pub metric_type: String,
+}
+
+impl MetricValue {
+    /// Returns the value as a duration when the metric is a timing metric.
+    pub fn duration(&self) -> Option<Duration> {
+        let value = u64::try_from(self.value).ok()?;
+        match self.metric_type.as_str() {
+            "timing" => Some(Duration::from_millis(value)),
+            "nsTiming" => Some(Duration::from_nanos(value)),
+            _ => None,
+        }
+    }
+}
+
+/// Metrics of a single physical operator of the executed plan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorMetrics {
+    pub name: String,
+    pub plan_id: i64,
+    pub parent: i64,
+    pub values: HashMap<String, MetricValue>,
+}
+
+impl OperatorMetrics {
+    pub fn rows_output(&self) -> Option<i64> {
+        self.values
+            .get(OUTPUT_ROWS_METRIC)
+            .map(|metric| metric.value)
+    }
+
+    /// Sum of all the timing metrics of this operator.
+    pub fn time_spent(&self) -> Duration {
+        self.values.values().filter_map(MetricValue::duration).sum()
+    }
+}
+
+/// Tree of the physical operators of an executed plan with their metrics.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryMetrics {
+    pub operators: Vec<OperatorMetrics>,
+}
+
+impl QueryMetrics {
+    /// An operator is a root when its parent was not reported, which is how
+    /// Spark marks the top-level operators (with a parent of `-1`), or when it
+    /// is its own parent.
+    pub fn is_root(&self, op: &OperatorMetrics) -> bool {
+        op.parent == op.plan_id
+            || !self
+                .operators
+                .iter()
+                .any(|parent| parent.plan_id == op.parent)
+    }
+
+    pub fn roots(&self) -> impl Iterator<Item = &OperatorMetrics> {
+        self.operators.iter().filter(|op| self.is_root(op))
+    }
+
+    pub fn children(&self, plan_id: i64) -> impl Iterator<Item = &OperatorMetrics> {
+        self.operators
+            .iter()
+            .filter(move |op| op.parent == plan_id && op.plan_id != plan_id)
+    }
+
+    /// Returns at most `n` operators, ordered by decreasing time spent.
+    pub fn slowest(&self, n: usize) -> Vec<&OperatorMetrics> {
+        let mut operators: Vec<&OperatorMetrics> = self.operators.iter().collect();
+        operators.sort_by_key(|op| std::cmp::Reverse(op.time_spent()));
+        operators.truncate(n);
+        operators
+    }
+
+    fn fmt_operator(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        op: &OperatorMetrics,
+        depth: usize,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{} (plan {})",
+            "",
+            op.name,
+            op.plan_id,
+            indent = depth * 2
+        )?;
+        let mut values: Vec<&MetricValue> = op.values.values().collect();
+        values.sort_by(|a, b| a.name.cmp(&b.name));
+        for (i, metric) in values.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{}{}: {}", separator, metric.name, metric.value)?;
+        }
+        writeln!(f)?;
+        for child in self.children(op.plan_id) {
+            self.fmt_operator(f, child, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the operator tree, one operator per line, children indented
+/// under their parent.
+impl fmt::Display for QueryMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for root in self.roots() {
+            self.fmt_operator(f, root, 0)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Metrics> for QueryMetrics {
+    fn from(metrics: Metrics) -> Self {
+        let operators = metrics
+            .metrics
+            .into_iter()
+            .map(|object| OperatorMetrics {
+                name: object.name,
+                plan_id: object.plan_id,
+                parent: object.parent,
+                values: object
+                    .execution_metrics
+                    .into_iter()
+                    .map(|(key, value)| {
+                        (
+                            key,
+                            MetricValue {
+                                name: value.name,
+                                value: value.value,
+                                metric_type: value.metric_type,
+                            },
+                        )
+                    })
+                    .collect(),
+            })
+            .collect();
+        QueryMetrics { operators }
+    }
+}
+
+/// Handle to the metrics observed by `DataFrame::observe`.
+///
+/// The values are filled in once an action over the observed `DataFrame`
+/// completes, in the same order as the expressions passed to `observe`.
+#[derive(Debug, Clone)]
+pub struct Observation {
+    name: String,
+    values: Rc<RefCell<Option<Vec<Literal>>>>,
+}
+
+impl Observation {
+    pub fn new(name: String) -> Self {
+        Observation {
+            name,
+            values: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns `None` until an action over the observed `DataFrame` completed.
+    pub fn values(&self) -> Option<Vec<Literal>> {
+        self.values.borrow().clone()
+    }
+
+    pub(crate) fn set_values(&self, values: Vec<Literal>) {
+        *self.values.borrow_mut() = Some(values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spark::execute_plan_response::metrics::{MetricObject, MetricValue as Value};
+
+    fn metric_object(name: &str, plan_id: i64, parent: i64, rows: i64) -> MetricObject {
+        MetricObject {
+            name: name.to_string(),
+            plan_id,
+            parent,
+            execution_metrics: HashMap::from([(
+                OUTPUT_ROWS_METRIC.to_string(),
+                Value {
+                    name: OUTPUT_ROWS_METRIC.to_string(),
+                    value: rows,
+                    metric_type: "sum".to_string(),
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_operators_without_reported_parent_are_roots() {
+        let metrics = QueryMetrics::from(Metrics {
+            metrics: vec![
+                metric_object("Project", 2, -1, 4),
+                metric_object("Filter", 1, 2, 4),
+                metric_object("LocalTableScan", 0, 1, 6),
+            ],
+        });
+
+        let roots: Vec<&str> = metrics.roots().map(|op| op.name.as_str()).collect();
+        assert_eq!(roots, vec!["Project"]);
+        assert_eq!(metrics.children(2).count(), 1);
+        assert_eq!(
+            metrics.to_string(),
+            "Project (plan 2) numOutputRows: 4\n  \
+             Filter (plan 1) numOutputRows: 4\n    \
+             LocalTableScan (plan 0) numOutputRows: 6\n"
+        );
+    }
+}
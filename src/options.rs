@@ -0,0 +1,199 @@
+//! Typed options of the built-in data sources, convertible into the options
+//! map accepted by `DataFrameReader::options`. Only the settings that are
+//! set are sent, so that Spark applies its own defaults for the others.
+use std::collections::HashMap;
+use std::fmt;
+
+/// How malformed records are handled while parsing CSV or JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Sets the malformed fields to null.
+    Permissive,
+    DropMalformed,
+    FailFast,
+}
+
+impl fmt::Display for ParseMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseMode::Permissive => "PERMISSIVE",
+            ParseMode::DropMalformed => "DROPMALFORMED",
+            ParseMode::FailFast => "FAILFAST",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub header: Option<bool>,
+    pub sep: Option<String>,
+    pub quote: Option<String>,
+    pub escape: Option<String>,
+    pub comment: Option<String>,
+    pub infer_schema: Option<bool>,
+    pub null_value: Option<String>,
+    pub date_format: Option<String>,
+    pub timestamp_format: Option<String>,
+    pub encoding: Option<String>,
+    pub multi_line: Option<bool>,
+    pub mode: Option<ParseMode>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    pub multi_line: Option<bool>,
+    pub primitives_as_string: Option<bool>,
+    pub prefers_decimal: Option<bool>,
+    pub allow_comments: Option<bool>,
+    pub allow_unquoted_field_names: Option<bool>,
+    pub allow_single_quotes: Option<bool>,
+    pub date_format: Option<String>,
+    pub timestamp_format: Option<String>,
+    pub encoding: Option<String>,
+    pub line_sep: Option<String>,
+    pub drop_field_if_all_null: Option<bool>,
+    pub mode: Option<ParseMode>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParquetOptions {
+    /// Merges the schemas of all the files instead of picking one.
+    pub merge_schema: Option<bool>,
+    pub datetime_rebase_mode: Option<String>,
+    pub int96_rebase_mode: Option<String>,
+}
+
+/// Splits a JDBC read on `column`: the range between the bounds is divided
+/// into `num_partitions` strides, the bounds do not filter any row.
+///
+/// The bounds are given as Spark parses them for the type of `column`, e.g.
+/// `3000` for a numeric column, `2023-01-01` for a date column or
+/// `2023-01-01 00:00:00` for a timestamp column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JdbcPartitioning {
+    pub column: String,
+    pub lower_bound: String,
+    pub upper_bound: String,
+    pub num_partitions: i32,
+}
+
+struct OptionsMap(HashMap<String, String>);
+
+impl OptionsMap {
+    fn new() -> Self {
+        OptionsMap(HashMap::new())
+    }
+
+    fn set<T: ToString>(mut self, key: &str, value: &Option<T>) -> Self {
+        if let Some(value) = value {
+            self.0.insert(key.to_string(), value.to_string());
+        }
+        self
+    }
+}
+
+impl From<CsvOptions> for HashMap<String, String> {
+    fn from(options: CsvOptions) -> Self {
+        OptionsMap::new()
+            .set("header", &options.header)
+            .set("sep", &options.sep)
+            .set("quote", &options.quote)
+            .set("escape", &options.escape)
+            .set("comment", &options.comment)
+            .set("inferSchema", &options.infer_schema)
+            .set("nullValue", &options.null_value)
+            .set("dateFormat", &options.date_format)
+            .set("timestampFormat", &options.timestamp_format)
+            .set("encoding", &options.encoding)
+            .set("multiLine", &options.multi_line)
+            .set("mode", &options.mode)
+            .0
+    }
+}
+
+impl From<JsonOptions> for HashMap<String, String> {
+    fn from(options: JsonOptions) -> Self {
+        OptionsMap::new()
+            .set("multiLine", &options.multi_line)
+            .set("primitivesAsString", &options.primitives_as_string)
+            .set("prefersDecimal", &options.prefers_decimal)
+            .set("allowComments", &options.allow_comments)
+            .set(
+                "allowUnquotedFieldNames",
+                &options.allow_unquoted_field_names,
+            )
+            .set("allowSingleQuotes", &options.allow_single_quotes)
+            .set("dateFormat", &options.date_format)
+            .set("timestampFormat", &options.timestamp_format)
+            .set("encoding", &options.encoding)
+            .set("lineSep", &options.line_sep)
+            .set("dropFieldIfAllNull", &options.drop_field_if_all_null)
+            .set("mode", &options.mode)
+            .0
+    }
+}
+
+impl From<ParquetOptions> for HashMap<String, String> {
+    fn from(options: ParquetOptions) -> Self {
+        OptionsMap::new()
+            .set("mergeSchema", &options.merge_schema)
+            .set("datetimeRebaseMode", &options.datetime_rebase_mode)
+            .set("int96RebaseMode", &options.int96_rebase_mode)
+            .0
+    }
+}
+
+impl From<JdbcPartitioning> for HashMap<String, String> {
+    fn from(partitioning: JdbcPartitioning) -> Self {
+        OptionsMap::new()
+            .set("partitionColumn", &Some(partitioning.column))
+            .set("lowerBound", &Some(partitioning.lower_bound))
+            .set("upperBound", &Some(partitioning.upper_bound))
+            .set("numPartitions", &Some(partitioning.num_partitions))
+            .0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_set_options_are_sent() {
+        let options: HashMap<String, String> = CsvOptions {
+            header: Some(true),
+            sep: Some(";".to_string()),
+            mode: Some(ParseMode::FailFast),
+            ..Default::default()
+        }
+        .into();
+        let expected: HashMap<String, String> = vec![
+            ("header".to_string(), "true".to_string()),
+            ("sep".to_string(), ";".to_string()),
+            ("mode".to_string(), "FAILFAST".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(options, expected);
+    }
+
+    #[test]
+    fn test_jdbc_partitioning_bounds_are_sent_as_given() {
+        let options: HashMap<String, String> = JdbcPartitioning {
+            column: "hired_on".to_string(),
+            lower_bound: "2023-01-01".to_string(),
+            upper_bound: "2023-12-31".to_string(),
+            num_partitions: 4,
+        }
+        .into();
+        let expected: HashMap<String, String> = vec![
+            ("partitionColumn".to_string(), "hired_on".to_string()),
+            ("lowerBound".to_string(), "2023-01-01".to_string()),
+            ("upperBound".to_string(), "2023-12-31".to_string()),
+            ("numPartitions".to_string(), "4".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(options, expected);
+    }
+}
@@ -18,6 +18,30 @@ pub struct LoadPlan {
     pub options: HashMap<String, String>,
     pub format: Option<String>,
     pub schema: Option<String>,
+    pub predicates: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct LocalRelation {
+    pub(crate) data: Vec<u8>,
+    pub(crate) schema: String,
+}
+
+/// Local data previously uploaded as a cache artifact, referenced by the hash
+/// of the serialized `LocalRelation`.
+#[derive(Clone)]
+pub struct CachedLocalRelation {
+    pub(crate) user_id: String,
+    pub(crate) session_id: String,
+    pub(crate) hash: String,
+}
+
+#[derive(Clone)]
+pub struct Range {
+    pub(crate) start: i64,
+    pub(crate) end: i64,
+    pub(crate) step: i64,
+    pub(crate) num_partitions: Option<i32>,
 }
 
 pub trait Plan {
@@ -33,6 +57,7 @@ impl Plan for SqlPlan {
             rel_type: Some(spark::relation::RelType::Sql(spark::Sql {
                 query: self.query.to_string(),
                 args: HashMap::new(),
+                pos_args: vec![],
             })),
         }
     }
@@ -51,6 +76,7 @@ impl Plan for NamedTableReadPlan {
                 unparsed_identifier: self.table_name.clone(),
                 options: self.options.clone(),
             })),
+            is_streaming: false,
         };
         spark::Relation {
             common: None,
@@ -74,8 +100,9 @@ impl Plan for LoadPlan {
                 schema: self.schema.clone(),
                 options: self.options.clone(),
                 format: self.format.clone(),
-                predicates: vec![],
+                predicates: self.predicates.clone(),
             })),
+            is_streaming: false,
         };
         spark::Relation {
             common: None,
@@ -89,6 +116,74 @@ impl Plan for LoadPlan {
             options: self.options.clone(),
             format: self.format.clone(),
             schema: self.schema.clone(),
+            predicates: self.predicates.clone(),
+        })
+    }
+}
+
+impl Plan for LocalRelation {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::LocalRelation(
+                spark::LocalRelation {
+                    data: Some(self.data.clone()),
+                    schema: Some(self.schema.clone()),
+                },
+            )),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(LocalRelation {
+            data: self.data.clone(),
+            schema: self.schema.clone(),
+        })
+    }
+}
+
+impl Plan for Range {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Range(spark::Range {
+                start: Some(self.start),
+                end: self.end,
+                step: self.step,
+                num_partitions: self.num_partitions,
+            })),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Range {
+            start: self.start,
+            end: self.end,
+            step: self.step,
+            num_partitions: self.num_partitions,
+        })
+    }
+}
+
+impl Plan for CachedLocalRelation {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::CachedLocalRelation(
+                spark::CachedLocalRelation {
+                    user_id: self.user_id.clone(),
+                    session_id: self.session_id.clone(),
+                    hash: self.hash.clone(),
+                },
+            )),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(CachedLocalRelation {
+            user_id: self.user_id.clone(),
+            session_id: self.session_id.clone(),
+            hash: self.hash.clone(),
         })
     }
 }
@@ -112,9 +207,625 @@ impl Plan for Project {
     }
 
     fn clone(&self) -> Box<dyn Plan> {
-        return Box::new(Project {
+        Box::new(Project {
             expressions: self.expressions.clone(),
             input: self.input.clone(),
-        });
+        })
+    }
+}
+
+pub struct Filter {
+    pub condition: spark::Expression,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Filter {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Filter(Box::new(spark::Filter {
+                input: Some(Box::new(self.input.collect())),
+                condition: Some(self.condition.clone()),
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Filter {
+            condition: self.condition.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Parse {
+    pub format: spark::parse::ParseFormat,
+    pub schema: Option<spark::DataType>,
+    pub options: HashMap<String, String>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Parse {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Parse(Box::new(spark::Parse {
+                input: Some(Box::new(self.input.collect())),
+                format: self.format.into(),
+                schema: self.schema.clone(),
+                options: self.options.clone(),
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Parse {
+            format: self.format,
+            schema: self.schema.clone(),
+            options: self.options.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Sample {
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub with_replacement: bool,
+    pub seed: Option<i64>,
+    pub deterministic_order: bool,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Sample {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Sample(Box::new(spark::Sample {
+                input: Some(Box::new(self.input.collect())),
+                lower_bound: self.lower_bound,
+                upper_bound: self.upper_bound,
+                with_replacement: Some(self.with_replacement),
+                seed: self.seed,
+                deterministic_order: self.deterministic_order,
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Sample {
+            lower_bound: self.lower_bound,
+            upper_bound: self.upper_bound,
+            with_replacement: self.with_replacement,
+            seed: self.seed,
+            deterministic_order: self.deterministic_order,
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct SampleBy {
+    pub col: spark::Expression,
+    pub fractions: Vec<spark::stat_sample_by::Fraction>,
+    pub seed: Option<i64>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for SampleBy {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::SampleBy(Box::new(
+                spark::StatSampleBy {
+                    input: Some(Box::new(self.input.collect())),
+                    col: Some(self.col.clone()),
+                    fractions: self.fractions.clone(),
+                    seed: self.seed,
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(SampleBy {
+            col: self.col.clone(),
+            fractions: self.fractions.clone(),
+            seed: self.seed,
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Summary {
+    pub statistics: Vec<String>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Summary {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Summary(Box::new(
+                spark::StatSummary {
+                    input: Some(Box::new(self.input.collect())),
+                    statistics: self.statistics.clone(),
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Summary {
+            statistics: self.statistics.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Describe {
+    pub cols: Vec<String>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Describe {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Describe(Box::new(
+                spark::StatDescribe {
+                    input: Some(Box::new(self.input.collect())),
+                    cols: self.cols.clone(),
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Describe {
+            cols: self.cols.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Crosstab {
+    pub col1: String,
+    pub col2: String,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Crosstab {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Crosstab(Box::new(
+                spark::StatCrosstab {
+                    input: Some(Box::new(self.input.collect())),
+                    col1: self.col1.clone(),
+                    col2: self.col2.clone(),
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Crosstab {
+            col1: self.col1.clone(),
+            col2: self.col2.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Cov {
+    pub col1: String,
+    pub col2: String,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Cov {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Cov(Box::new(spark::StatCov {
+                input: Some(Box::new(self.input.collect())),
+                col1: self.col1.clone(),
+                col2: self.col2.clone(),
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Cov {
+            col1: self.col1.clone(),
+            col2: self.col2.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Corr {
+    pub col1: String,
+    pub col2: String,
+    pub method: Option<String>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Corr {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Corr(Box::new(spark::StatCorr {
+                input: Some(Box::new(self.input.collect())),
+                col1: self.col1.clone(),
+                col2: self.col2.clone(),
+                method: self.method.clone(),
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Corr {
+            col1: self.col1.clone(),
+            col2: self.col2.clone(),
+            method: self.method.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct ApproxQuantile {
+    pub cols: Vec<String>,
+    pub probabilities: Vec<f64>,
+    pub relative_error: f64,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for ApproxQuantile {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::ApproxQuantile(Box::new(
+                spark::StatApproxQuantile {
+                    input: Some(Box::new(self.input.collect())),
+                    cols: self.cols.clone(),
+                    probabilities: self.probabilities.clone(),
+                    relative_error: self.relative_error,
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(ApproxQuantile {
+            cols: self.cols.clone(),
+            probabilities: self.probabilities.clone(),
+            relative_error: self.relative_error,
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct FreqItems {
+    pub cols: Vec<String>,
+    pub support: Option<f64>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for FreqItems {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::FreqItems(Box::new(
+                spark::StatFreqItems {
+                    input: Some(Box::new(self.input.collect())),
+                    cols: self.cols.clone(),
+                    support: self.support,
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(FreqItems {
+            cols: self.cols.clone(),
+            support: self.support,
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Repartition {
+    pub num_partitions: i32,
+    pub shuffle: bool,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Repartition {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Repartition(Box::new(
+                spark::Repartition {
+                    input: Some(Box::new(self.input.collect())),
+                    num_partitions: self.num_partitions,
+                    shuffle: Some(self.shuffle),
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Repartition {
+            num_partitions: self.num_partitions,
+            shuffle: self.shuffle,
+            input: self.input.clone(),
+        })
+    }
+}
+
+/// Hash partitioning, or range partitioning when all the expressions are
+/// sort orders.
+pub struct RepartitionByExpression {
+    pub partition_exprs: Vec<spark::Expression>,
+    pub num_partitions: Option<i32>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for RepartitionByExpression {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::RepartitionByExpression(Box::new(
+                spark::RepartitionByExpression {
+                    input: Some(Box::new(self.input.collect())),
+                    partition_exprs: self.partition_exprs.clone(),
+                    num_partitions: self.num_partitions,
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(RepartitionByExpression {
+            partition_exprs: self.partition_exprs.clone(),
+            num_partitions: self.num_partitions,
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct SubqueryAlias {
+    pub alias: String,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for SubqueryAlias {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::SubqueryAlias(Box::new(
+                spark::SubqueryAlias {
+                    input: Some(Box::new(self.input.collect())),
+                    alias: self.alias.clone(),
+                    qualifier: vec![],
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(SubqueryAlias {
+            alias: self.alias.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Join {
+    pub left: Box<dyn Plan>,
+    pub right: Box<dyn Plan>,
+    pub join_condition: Option<spark::Expression>,
+    pub join_type: spark::join::JoinType,
+}
+
+impl Plan for Join {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Join(Box::new(spark::Join {
+                left: Some(Box::new(self.left.collect())),
+                right: Some(Box::new(self.right.collect())),
+                join_condition: self.join_condition.clone(),
+                join_type: self.join_type.into(),
+                using_columns: vec![],
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Join {
+            left: self.left.clone(),
+            right: self.right.clone(),
+            join_condition: self.join_condition.clone(),
+            join_type: self.join_type,
+        })
+    }
+}
+
+pub struct Hint {
+    pub name: String,
+    pub parameters: Vec<spark::Expression>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Hint {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Hint(Box::new(spark::Hint {
+                input: Some(Box::new(self.input.collect())),
+                name: self.name.clone(),
+                parameters: self.parameters.clone(),
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Hint {
+            name: self.name.clone(),
+            parameters: self.parameters.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct Unpivot {
+    pub ids: Vec<spark::Expression>,
+    pub values: Option<Vec<spark::Expression>>,
+    pub variable_column_name: String,
+    pub value_column_name: String,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for Unpivot {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Unpivot(Box::new(
+                spark::Unpivot {
+                    input: Some(Box::new(self.input.collect())),
+                    ids: self.ids.clone(),
+                    values: self
+                        .values
+                        .clone()
+                        .map(|values| spark::unpivot::Values { values }),
+                    variable_column_name: self.variable_column_name.clone(),
+                    value_column_name: self.value_column_name.clone(),
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(Unpivot {
+            ids: self.ids.clone(),
+            values: self.values.clone(),
+            variable_column_name: self.variable_column_name.clone(),
+            value_column_name: self.value_column_name.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct NaFill {
+    pub cols: Vec<String>,
+    pub values: Vec<spark::expression::Literal>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for NaFill {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::FillNa(Box::new(spark::NaFill {
+                input: Some(Box::new(self.input.collect())),
+                cols: self.cols.clone(),
+                values: self.values.clone(),
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(NaFill {
+            cols: self.cols.clone(),
+            values: self.values.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct NaDrop {
+    pub cols: Vec<String>,
+    pub min_non_nulls: Option<i32>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for NaDrop {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::DropNa(Box::new(spark::NaDrop {
+                input: Some(Box::new(self.input.collect())),
+                cols: self.cols.clone(),
+                min_non_nulls: self.min_non_nulls,
+            }))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(NaDrop {
+            cols: self.cols.clone(),
+            min_non_nulls: self.min_non_nulls,
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct NaReplace {
+    pub cols: Vec<String>,
+    pub replacements: Vec<spark::na_replace::Replacement>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for NaReplace {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::Replace(Box::new(
+                spark::NaReplace {
+                    input: Some(Box::new(self.input.collect())),
+                    cols: self.cols.clone(),
+                    replacements: self.replacements.clone(),
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(NaReplace {
+            cols: self.cols.clone(),
+            replacements: self.replacements.clone(),
+            input: self.input.clone(),
+        })
+    }
+}
+
+pub struct CollectMetrics {
+    pub name: String,
+    pub metrics: Vec<spark::Expression>,
+    pub input: Box<dyn Plan>,
+}
+
+impl Plan for CollectMetrics {
+    fn collect(&self) -> spark::Relation {
+        spark::Relation {
+            common: None,
+            rel_type: Some(spark::relation::RelType::CollectMetrics(Box::new(
+                spark::CollectMetrics {
+                    input: Some(Box::new(self.input.collect())),
+                    name: self.name.clone(),
+                    metrics: self.metrics.clone(),
+                },
+            ))),
+        }
+    }
+
+    fn clone(&self) -> Box<dyn Plan> {
+        Box::new(CollectMetrics {
+            name: self.name.clone(),
+            metrics: self.metrics.clone(),
+            input: self.input.clone(),
+        })
     }
 }
@@ -1,23 +1,44 @@
-use crate::arrow::deserialize;
+use crate::arrow::{deserialize, serialize};
+use crate::artifact::{self, cache_artifact_name, Artifact};
+use crate::catalog::Catalog;
 use crate::dataframe::{DataFrame, DataFrameReader};
-use crate::error::{NotImplementedYetError, SparkSessionCreationError};
+use crate::de;
+use crate::error::{InvalidArgumentError, NotImplementedYetError, SparkSessionCreationError};
 use crate::error::{SparkError, UnexpectedError};
-use crate::plan::SqlPlan;
+use crate::metrics::{Observation, QueryMetrics, QueryResult};
+use crate::plan::{CachedLocalRelation, LocalRelation, Plan, Range, SqlPlan};
 use crate::spark;
 use crate::spark::execute_plan_response::ArrowBatch;
 use crate::spark::execute_plan_response::Metrics;
+use crate::spark::execute_plan_response::ObservedMetrics;
 use crate::spark::DataType;
 use crate::spark::ExecutePlanResponse;
+use crate::types::{from_arrow_schema, to_arrow_schema, to_ddl};
+use crate::udf::UDFRegistration;
+use arrow::json::reader::infer_json_schema_from_iterator;
+use arrow::json::ReaderBuilder;
 use arrow::record_batch::RecordBatch;
+use futures::stream::{self, Stream, TryStreamExt};
+use prost::Message;
 use prost_types::Any;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use spark::spark_connect_service_client::SparkConnectServiceClient;
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
 use tokio::sync::Mutex;
 use tonic::transport::Channel;
 use tonic::Request;
 use tonic::Streaming;
 use uuid::Uuid;
 
+/// Local data larger than this, in bytes, is uploaded as a cache artifact
+/// instead of being inlined in the plan. Coherent with Python Spark Connect.
+const DEFAULT_LOCAL_RELATION_CACHE_THRESHOLD: usize = 64 * 1024 * 1024;
+
 pub struct Context {
     pub user_id: String,
     pub user_name: String,
@@ -29,6 +50,9 @@ pub struct RemoteSparkSession {
     client: Mutex<SparkConnectServiceClient<Channel>>,
     session_id: String,
     context: Option<Context>,
+    observations: RefCell<HashMap<String, Observation>>,
+    local_relation_cache_threshold: Cell<usize>,
+    cached_artifacts: RefCell<HashSet<String>>,
 }
 
 impl RemoteSparkSession {
@@ -41,6 +65,9 @@ impl RemoteSparkSession {
             client: Mutex::new(client),
             session_id: Uuid::new_v4().to_string(), // Coherent with Python Spark Connect
             context: user_context,
+            observations: RefCell::new(HashMap::new()),
+            local_relation_cache_threshold: Cell::new(DEFAULT_LOCAL_RELATION_CACHE_THRESHOLD),
+            cached_artifacts: RefCell::new(HashSet::new()),
         }))
     }
 
@@ -55,16 +82,111 @@ impl RemoteSparkSession {
         }
     }
 
+    /// Creates a `DataFrame` with a single `id` column of `BIGINT` values from
+    /// `start` to `end`, exclusive, incremented by `step`. The number of
+    /// partitions defaults to the default parallelism of the server.
+    pub fn range(
+        self: Rc<Self>,
+        start: i64,
+        end: i64,
+        step: i64,
+        num_partitions: Option<i32>,
+    ) -> DataFrame {
+        DataFrame {
+            session: self,
+            plan: Box::new(Range {
+                start,
+                end,
+                step,
+                num_partitions,
+            }),
+        }
+    }
+
+    /// Creates a `DataFrame` from local data, sent to the server as an Arrow
+    /// IPC stream. Data up to the local relation cache threshold is inlined in
+    /// the plan, larger data is uploaded once as a cache artifact and
+    /// referenced by its hash.
+    pub async fn create_dataframe(
+        self: Rc<Self>,
+        batches: Vec<RecordBatch>,
+    ) -> Result<DataFrame, SparkError> {
+        let data = serialize(&batches)?;
+        let schema = to_ddl(&from_arrow_schema(&batches[0].schema())?)?;
+        let plan: Box<dyn Plan> = if data.len() > self.local_relation_cache_threshold.get() {
+            let relation = spark::LocalRelation {
+                data: Some(data),
+                schema: Some(schema),
+            };
+            let hash = self.cache_artifact(relation.encode_to_vec()).await?;
+            Box::new(CachedLocalRelation {
+                user_id: self
+                    .context
+                    .as_ref()
+                    .map(|ctx| ctx.user_id.clone())
+                    .unwrap_or_default(),
+                session_id: self.session_id.clone(),
+                hash,
+            })
+        } else {
+            Box::new(LocalRelation { data, schema })
+        };
+        Ok(DataFrame {
+            session: self,
+            plan,
+        })
+    }
+
+    /// Sets the size, in bytes, above which local data passed to
+    /// `create_dataframe` is uploaded as a cache artifact. Defaults to 64 MiB.
+    pub fn set_local_relation_cache_threshold(&self, threshold: usize) {
+        self.local_relation_cache_threshold.set(threshold);
+    }
+
+    /// Creates a `DataFrame` from serializable rows, inferring the schema from
+    /// their JSON representation: integers become `BIGINT`, floats `DOUBLE`,
+    /// sequences `ARRAY` and structs or maps `STRUCT`. Without rows there is
+    /// nothing to infer from and the `DataFrame` is empty, with no columns.
+    pub async fn create_dataframe_from_rows<T: Serialize>(
+        self: Rc<Self>,
+        rows: &[T],
+    ) -> Result<DataFrame, SparkError> {
+        let values = rows
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| SparkError::InvalidArgument(InvalidArgumentError(err.to_string())))?;
+        let schema = Arc::new(infer_json_schema_from_iterator(values.iter().map(Ok))?);
+        let mut decoder = ReaderBuilder::new(schema.clone())
+            .with_batch_size(rows.len().max(1))
+            .build_decoder()?;
+        decoder.serialize(rows)?;
+        // Without rows the decoder has nothing to flush, an empty batch still
+        // carries the schema to the server.
+        let batch = decoder
+            .flush()?
+            .unwrap_or_else(|| RecordBatch::new_empty(schema));
+        self.create_dataframe(vec![batch]).await
+    }
+
     pub fn read(self: Rc<Self>) -> DataFrameReader {
         DataFrameReader::new(self)
     }
 
+    pub fn catalog(self: Rc<Self>) -> Catalog {
+        Catalog::new(self)
+    }
+
+    pub fn udf(self: Rc<Self>) -> UDFRegistration {
+        UDFRegistration::new(self)
+    }
+
     fn internal_user_context(&self) -> Option<spark::UserContext> {
-        return self.context.as_ref().map(|uc| spark::UserContext {
+        self.context.as_ref().map(|uc| spark::UserContext {
             user_id: uc.user_id.clone(),
             user_name: uc.user_name.clone(),
             extensions: uc.extensions.clone(),
-        });
+        })
     }
 
     fn build_request(&self, opt_type: spark::plan::OpType) -> spark::ExecutePlanRequest {
@@ -75,6 +197,9 @@ impl RemoteSparkSession {
             plan: Some(spark::Plan {
                 op_type: Some(opt_type),
             }),
+            operation_id: None,
+            request_options: vec![],
+            tags: vec![],
         }
     }
 
@@ -91,21 +216,208 @@ impl RemoteSparkSession {
     pub(crate) async fn save(
         &self,
         write_operation: spark::WriteOperation,
+    ) -> Result<(), SparkError> {
+        self.execute_command(spark::command::CommandType::WriteOperation(write_operation))
+            .await
+    }
+
+    pub(crate) async fn execute_command(
+        &self,
+        command_type: spark::command::CommandType,
     ) -> Result<(), SparkError> {
         let cmd = spark::Command {
-            command_type: Some(spark::command::CommandType::WriteOperation(write_operation)),
+            command_type: Some(command_type),
         };
-        self.execute_opt(spark::plan::OpType::Command(cmd)).await?;
+        self.execute(spark::plan::OpType::Command(cmd)).await?;
         Ok(())
     }
 
-    pub(crate) async fn fetch(&self, rel: spark::Relation) -> Result<Vec<RecordBatch>, SparkError> {
-        let mut stream = self.execute_opt(spark::plan::OpType::Root(rel)).await?;
+    async fn execute(&self, opt_type: spark::plan::OpType) -> Result<Collector, SparkError> {
+        let mut stream = self.execute_opt(opt_type).await?;
         let mut collector = Collector::new();
         while let Some(resp) = stream.message().await? {
             collector.process(&resp)?;
         }
-        collector.records()
+        self.publish_observed_metrics(collector.observed_metrics.drain(..));
+        Ok(collector)
+    }
+
+    pub(crate) async fn fetch(&self, rel: spark::Relation) -> Result<Vec<RecordBatch>, SparkError> {
+        self.execute(spark::plan::OpType::Root(rel))
+            .await?
+            .records()
+    }
+
+    /// Fetches the value of a one row, one column result, as answered by
+    /// catalog operations and scalar statistics.
+    pub(crate) async fn fetch_scalar<T: DeserializeOwned>(
+        &self,
+        rel: spark::Relation,
+    ) -> Result<T, SparkError> {
+        let rows: Vec<(T,)> = de::from_record_batches(&self.fetch(rel).await?)?;
+        rows.into_iter()
+            .next()
+            .map(|(value,)| value)
+            .ok_or(SparkError::EmptyResponse)
+    }
+
+    pub(crate) async fn fetch_with_metrics(
+        &self,
+        rel: spark::Relation,
+    ) -> Result<QueryResult, SparkError> {
+        self.execute(spark::plan::OpType::Root(rel))
+            .await?
+            .query_result()
+    }
+
+    /// Streams the record batches as the server sends them, instead of
+    /// waiting for the whole result. Observed metrics are published as soon
+    /// as they are received.
+    pub(crate) async fn fetch_stream(
+        self: Rc<Self>,
+        rel: spark::Relation,
+    ) -> Result<impl Stream<Item = Result<RecordBatch, SparkError>>, SparkError> {
+        let responses = self.execute_opt(spark::plan::OpType::Root(rel)).await?;
+        Ok(responses
+            .map_err(SparkError::from)
+            .inspect_ok(move |response| {
+                self.publish_observed_metrics(response.observed_metrics.iter().cloned())
+            })
+            .try_filter_map(|response| async move {
+                match response.response_type {
+                    Some(spark::execute_plan_response::ResponseType::ArrowBatch(batch)) => {
+                        let batches = deserialize(batch)?;
+                        Ok(Some(stream::iter(batches.into_iter().map(Ok))))
+                    }
+                    _ => Ok(None),
+                }
+            })
+            .try_flatten())
+    }
+
+    /// Uploads the data as a cache artifact unless this session already
+    /// holds it, and returns its hash.
+    async fn cache_artifact(&self, data: Vec<u8>) -> Result<String, SparkError> {
+        let (hash, artifact) = Artifact::cache(data);
+        if self.cached_artifacts.borrow().contains(&hash) {
+            return Ok(hash);
+        }
+        let name = cache_artifact_name(&hash);
+        let exists = self
+            .artifact_status(vec![name.clone()])
+            .await?
+            .get(&name)
+            .copied()
+            .unwrap_or(false);
+        if !exists {
+            self.upload_artifacts(vec![artifact]).await?;
+        }
+        self.cached_artifacts.borrow_mut().insert(hash.clone());
+        Ok(hash)
+    }
+
+    /// Adds local `.jar` or `.class` files to the session, making them
+    /// available to the server, e.g. for Java UDFs or data sources.
+    pub async fn add_artifacts(&self, paths: Vec<String>) -> Result<(), SparkError> {
+        let artifacts = paths
+            .iter()
+            .map(|path| Artifact::from_extension(path))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.upload_artifacts(artifacts).await
+    }
+
+    /// Adds a local jar to the classpath of the session.
+    pub async fn add_jar(&self, path: String) -> Result<(), SparkError> {
+        let artifact = Artifact::from_path(artifact::JAR_PREFIX, &path)?;
+        self.upload_artifacts(vec![artifact]).await
+    }
+
+    /// Adds a local file, to be retrieved on the server with `SparkFiles.get`.
+    pub async fn add_file(&self, path: String) -> Result<(), SparkError> {
+        let artifact = Artifact::from_path(artifact::FILE_PREFIX, &path)?;
+        self.upload_artifacts(vec![artifact]).await
+    }
+
+    /// Adds a local archive, extracted on the server into the working
+    /// directory of each executor.
+    pub async fn add_archive(&self, path: String) -> Result<(), SparkError> {
+        let artifact = Artifact::from_path(artifact::ARCHIVE_PREFIX, &path)?;
+        self.upload_artifacts(vec![artifact]).await
+    }
+
+    async fn upload_artifacts(&self, artifacts: Vec<Artifact>) -> Result<(), SparkError> {
+        let requests: Vec<spark::AddArtifactsRequest> = artifact::payloads(artifacts)
+            .into_iter()
+            .map(|payload| spark::AddArtifactsRequest {
+                session_id: self.session_id.clone(),
+                user_context: self.internal_user_context(),
+                client_type: self.context.as_ref().map(|ctx| ctx.client_type.clone()),
+                payload: Some(payload),
+            })
+            .collect();
+        let mut client = self.client.lock().await;
+        let res = client
+            .add_artifacts(Request::new(stream::iter(requests)))
+            .await?;
+        for summary in res.into_inner().artifacts {
+            if !summary.is_crc_successful {
+                return Err(SparkError::Unexpected(UnexpectedError(format!(
+                    "CRC check failed while uploading artifact {}",
+                    summary.name
+                ))));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reports, for each artifact name such as `cache/<hash>`, whether the
+    /// server already holds it. Spark only tracks cache artifacts, any other
+    /// name is reported as missing.
+    pub async fn artifact_status(
+        &self,
+        names: Vec<String>,
+    ) -> Result<HashMap<String, bool>, SparkError> {
+        let req = spark::ArtifactStatusesRequest {
+            session_id: self.session_id.clone(),
+            user_context: self.internal_user_context(),
+            client_type: self.context.as_ref().map(|ctx| ctx.client_type.clone()),
+            names,
+        };
+        let mut client = self.client.lock().await;
+        let res = client.artifact_status(Request::new(req)).await?;
+        Ok(res
+            .into_inner()
+            .statuses
+            .into_iter()
+            .map(|(name, status)| (name, status.exists))
+            .collect())
+    }
+
+    /// Registers `observation` until the metrics observed under its name are
+    /// received. Like PySpark, two pending observations cannot share a name.
+    pub(crate) fn register_observation(&self, observation: &Observation) -> Result<(), SparkError> {
+        match self
+            .observations
+            .borrow_mut()
+            .entry(observation.name().to_string())
+        {
+            Entry::Occupied(entry) => Err(SparkError::InvalidArgument(InvalidArgumentError(
+                format!("An observation named {} is already registered", entry.key()),
+            ))),
+            Entry::Vacant(entry) => {
+                entry.insert(observation.clone());
+                Ok(())
+            }
+        }
+    }
+
+    fn publish_observed_metrics(&self, observed: impl Iterator<Item = ObservedMetrics>) {
+        let mut observations = self.observations.borrow_mut();
+        for metrics in observed {
+            if let Some(observation) = observations.remove(&metrics.name) {
+                observation.set_values(metrics.values);
+            }
+        }
     }
 }
 
@@ -114,6 +426,7 @@ pub struct Collector {
     schema: Option<DataType>,
     data: Option<ArrowBatch>,
     metrics: Option<Metrics>,
+    observed_metrics: Vec<ObservedMetrics>,
 }
 
 impl Collector {
@@ -122,6 +435,7 @@ impl Collector {
             schema: None,
             data: None,
             metrics: None,
+            observed_metrics: vec![],
         }
     }
 
@@ -132,11 +446,14 @@ impl Collector {
         if let Some(metrics) = response.metrics.as_ref() {
             self.metrics = Some(metrics.clone());
         }
+        self.observed_metrics
+            .extend(response.observed_metrics.iter().cloned());
         if let Some(data) = response.response_type.as_ref() {
             match data {
                 spark::execute_plan_response::ResponseType::ArrowBatch(batch) => {
                     self.data = Some(batch.clone());
                 }
+                spark::execute_plan_response::ResponseType::ResultComplete(_) => {}
                 _ => {
                     return Err(SparkError::NotImplementedYet(NotImplementedYetError(
                         format!("Unhandled response type {:?}", self),
@@ -151,9 +468,23 @@ impl Collector {
         if let Some(data) = self.data.take() {
             return deserialize(data);
         }
+        if let Some(schema) = self.schema.as_ref() {
+            let schema = to_arrow_schema(schema)?;
+            return Ok(vec![RecordBatch::new_empty(Arc::new(schema))]);
+        }
         Err(SparkError::Unexpected(UnexpectedError(format!(
             "Unexpected state {:?}",
             &self
         ))))
     }
+
+    fn query_result(mut self) -> Result<QueryResult, SparkError> {
+        let metrics = self
+            .metrics
+            .take()
+            .map(QueryMetrics::from)
+            .unwrap_or_default();
+        let batches = self.records()?;
+        Ok(QueryResult { batches, metrics })
+    }
 }
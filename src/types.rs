@@ -0,0 +1,547 @@
+use crate::error::{InvalidArgumentError, NotImplementedYetError, SparkError};
+use crate::spark;
+use crate::spark::data_type::Kind;
+use arrow::datatypes::{DataType, Field, Fields, IntervalUnit, Schema, TimeUnit};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Metadata key used by Spark to preserve char/varchar types of string fields.
+const CHAR_VARCHAR_METADATA_KEY: &str = "__CHAR_VARCHAR_TYPE_STRING";
+
+/// Arrow timestamps are instants, the zone only affects how they are displayed.
+const TIME_ZONE: &str = "UTC";
+
+const DEFAULT_DECIMAL_PRECISION: i32 = 10;
+const DEFAULT_DECIMAL_SCALE: i32 = 0;
+
+// Field ordinals of YearMonthIntervalType and DayTimeIntervalType
+const YEAR: i32 = 0;
+const MONTH: i32 = 1;
+pub(crate) const DAY: i32 = 0;
+pub(crate) const SECOND: i32 = 3;
+
+pub fn to_arrow_schema(data_type: &spark::DataType) -> Result<Schema, SparkError> {
+    match data_type.kind.as_ref() {
+        Some(Kind::Struct(st)) => {
+            let fields = st
+                .fields
+                .iter()
+                .map(to_arrow_field)
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(Schema::new(fields))
+        }
+        _ => Err(unsupported(format!(
+            "Only struct types can be converted to a schema, got {:?}",
+            data_type
+        ))),
+    }
+}
+
+pub fn to_arrow_field(field: &spark::data_type::StructField) -> Result<Field, SparkError> {
+    let data_type = field
+        .data_type
+        .as_ref()
+        .ok_or_else(|| unsupported(format!("Missing data type for field {}", field.name)))?;
+    let arrow_field = Field::new(
+        field.name.clone(),
+        to_arrow_data_type(data_type)?,
+        field.nullable,
+    );
+    let char_varchar = match data_type.kind.as_ref() {
+        Some(Kind::Char(c)) => Some(format!("char({})", c.length)),
+        Some(Kind::VarChar(vc)) => Some(format!("varchar({})", vc.length)),
+        _ => None,
+    };
+    Ok(match char_varchar {
+        Some(type_string) => arrow_field.with_metadata(HashMap::from([(
+            CHAR_VARCHAR_METADATA_KEY.to_string(),
+            type_string,
+        )])),
+        None => arrow_field,
+    })
+}
+
+/// Converts a Spark type to the Arrow type Spark uses to serialize it.
+///
+/// Char and varchar become plain strings and user defined types are
+/// converted through their SQL type, as Spark does.
+pub fn to_arrow_data_type(data_type: &spark::DataType) -> Result<DataType, SparkError> {
+    let kind = data_type
+        .kind
+        .as_ref()
+        .ok_or_else(|| unsupported("Missing data type kind".to_string()))?;
+    let arrow_type = match kind {
+        Kind::Null(_) => DataType::Null,
+        Kind::Binary(_) => DataType::Binary,
+        Kind::Boolean(_) => DataType::Boolean,
+        Kind::Byte(_) => DataType::Int8,
+        Kind::Short(_) => DataType::Int16,
+        Kind::Integer(_) => DataType::Int32,
+        Kind::Long(_) => DataType::Int64,
+        Kind::Float(_) => DataType::Float32,
+        Kind::Double(_) => DataType::Float64,
+        Kind::Decimal(decimal) => {
+            let precision = decimal.precision.unwrap_or(DEFAULT_DECIMAL_PRECISION);
+            let scale = decimal.scale.unwrap_or(DEFAULT_DECIMAL_SCALE);
+            match (u8::try_from(precision), i8::try_from(scale)) {
+                (Ok(precision), Ok(scale)) => DataType::Decimal128(precision, scale),
+                _ => {
+                    return Err(invalid(format!(
+                        "Invalid decimal precision {} or scale {}",
+                        precision, scale
+                    )))
+                }
+            }
+        }
+        Kind::String(_) | Kind::Char(_) | Kind::VarChar(_) => DataType::Utf8,
+        Kind::Date(_) => DataType::Date32,
+        Kind::Timestamp(_) => DataType::Timestamp(TimeUnit::Microsecond, Some(TIME_ZONE.into())),
+        Kind::TimestampNtz(_) => DataType::Timestamp(TimeUnit::Microsecond, None),
+        Kind::CalendarInterval(_) => DataType::Interval(IntervalUnit::MonthDayNano),
+        Kind::YearMonthInterval(_) => DataType::Interval(IntervalUnit::YearMonth),
+        Kind::DayTimeInterval(_) => DataType::Duration(TimeUnit::Microsecond),
+        Kind::Array(array) => {
+            let element_type = array
+                .element_type
+                .as_ref()
+                .ok_or_else(|| unsupported("Missing array element type".to_string()))?;
+            DataType::List(Arc::new(Field::new(
+                "element",
+                to_arrow_data_type(element_type)?,
+                array.contains_null,
+            )))
+        }
+        Kind::Struct(st) => DataType::Struct(
+            st.fields
+                .iter()
+                .map(to_arrow_field)
+                .collect::<Result<Fields, _>>()?,
+        ),
+        Kind::Map(map) => {
+            let (key_type, value_type) = match (map.key_type.as_ref(), map.value_type.as_ref()) {
+                (Some(key_type), Some(value_type)) => (key_type, value_type),
+                _ => return Err(unsupported("Missing map key or value type".to_string())),
+            };
+            let entries = Fields::from(vec![
+                Field::new("key", to_arrow_data_type(key_type)?, false),
+                Field::new(
+                    "value",
+                    to_arrow_data_type(value_type)?,
+                    map.value_contains_null,
+                ),
+            ]);
+            DataType::Map(
+                Arc::new(Field::new("entries", DataType::Struct(entries), false)),
+                false,
+            )
+        }
+        Kind::Udt(udt) => {
+            let sql_type = udt.sql_type.as_ref().ok_or_else(|| {
+                unsupported(format!(
+                    "Missing SQL type for user defined type {}",
+                    udt.r#type
+                ))
+            })?;
+            to_arrow_data_type(sql_type)?
+        }
+        Kind::Unparsed(unparsed) => {
+            return Err(unsupported(format!(
+                "Unparsed data type {}",
+                unparsed.data_type_string
+            )))
+        }
+    };
+    Ok(arrow_type)
+}
+
+/// Formats a Spark type as DDL. Struct types are formatted as a list of
+/// columns, as accepted by Spark wherever a schema string is expected, except
+/// for the empty struct, which has no columns to list.
+pub fn to_ddl(data_type: &spark::DataType) -> Result<String, SparkError> {
+    match data_type.kind.as_ref() {
+        Some(Kind::Struct(st)) if st.fields.is_empty() => Ok("STRUCT<>".to_string()),
+        Some(Kind::Struct(st)) => Ok(st
+            .fields
+            .iter()
+            .map(|field| ddl_field(field, " "))
+            .collect::<Result<Vec<_>, _>>()?
+            .join(", ")),
+        _ => ddl_type(data_type),
+    }
+}
+
+fn ddl_field(field: &spark::data_type::StructField, separator: &str) -> Result<String, SparkError> {
+    let data_type = field
+        .data_type
+        .as_ref()
+        .ok_or_else(|| unsupported(format!("Missing data type for field {}", field.name)))?;
+    let not_null = if field.nullable { "" } else { " NOT NULL" };
+    Ok(format!(
+        "`{}`{}{}{}",
+        field.name.replace('`', "``"),
+        separator,
+        ddl_type(data_type)?,
+        not_null
+    ))
+}
+
+fn ddl_type(data_type: &spark::DataType) -> Result<String, SparkError> {
+    let kind = data_type
+        .kind
+        .as_ref()
+        .ok_or_else(|| unsupported("Missing data type kind".to_string()))?;
+    let ddl = match kind {
+        Kind::Null(_) => "VOID".to_string(),
+        Kind::Binary(_) => "BINARY".to_string(),
+        Kind::Boolean(_) => "BOOLEAN".to_string(),
+        Kind::Byte(_) => "TINYINT".to_string(),
+        Kind::Short(_) => "SMALLINT".to_string(),
+        Kind::Integer(_) => "INT".to_string(),
+        Kind::Long(_) => "BIGINT".to_string(),
+        Kind::Float(_) => "FLOAT".to_string(),
+        Kind::Double(_) => "DOUBLE".to_string(),
+        Kind::Decimal(decimal) => format!(
+            "DECIMAL({}, {})",
+            decimal.precision.unwrap_or(DEFAULT_DECIMAL_PRECISION),
+            decimal.scale.unwrap_or(DEFAULT_DECIMAL_SCALE)
+        ),
+        Kind::String(_) => "STRING".to_string(),
+        Kind::Char(c) => format!("CHAR({})", c.length),
+        Kind::VarChar(vc) => format!("VARCHAR({})", vc.length),
+        Kind::Date(_) => "DATE".to_string(),
+        Kind::Timestamp(_) => "TIMESTAMP".to_string(),
+        Kind::TimestampNtz(_) => "TIMESTAMP_NTZ".to_string(),
+        Kind::CalendarInterval(_) => "INTERVAL".to_string(),
+        Kind::YearMonthInterval(interval) => interval_ddl(
+            &["YEAR", "MONTH"],
+            interval.start_field.unwrap_or(YEAR),
+            interval.end_field.unwrap_or(MONTH),
+        )?,
+        Kind::DayTimeInterval(interval) => interval_ddl(
+            &["DAY", "HOUR", "MINUTE", "SECOND"],
+            interval.start_field.unwrap_or(DAY),
+            interval.end_field.unwrap_or(SECOND),
+        )?,
+        Kind::Array(array) => match array.element_type.as_ref() {
+            Some(element_type) => format!("ARRAY<{}>", ddl_type(element_type)?),
+            None => return Err(unsupported("Missing array element type".to_string())),
+        },
+        Kind::Struct(st) => format!(
+            "STRUCT<{}>",
+            st.fields
+                .iter()
+                .map(|field| ddl_field(field, ": "))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(", ")
+        ),
+        Kind::Map(map) => match (map.key_type.as_ref(), map.value_type.as_ref()) {
+            (Some(key_type), Some(value_type)) => {
+                format!("MAP<{}, {}>", ddl_type(key_type)?, ddl_type(value_type)?)
+            }
+            _ => return Err(unsupported("Missing map key or value type".to_string())),
+        },
+        Kind::Udt(udt) => match udt.sql_type.as_ref() {
+            Some(sql_type) => ddl_type(sql_type)?,
+            None => {
+                return Err(unsupported(format!(
+                    "Missing SQL type for user defined type {}",
+                    udt.r#type
+                )))
+            }
+        },
+        Kind::Unparsed(unparsed) => unparsed.data_type_string.clone(),
+    };
+    Ok(ddl)
+}
+
+fn interval_ddl(units: &[&str], start: i32, end: i32) -> Result<String, SparkError> {
+    let unit = |field: i32| {
+        usize::try_from(field)
+            .ok()
+            .and_then(|field| units.get(field))
+            .ok_or_else(|| unsupported(format!("Invalid interval field {}", field)))
+    };
+    if start == end {
+        Ok(format!("INTERVAL {}", unit(start)?))
+    } else {
+        Ok(format!("INTERVAL {} TO {}", unit(start)?, unit(end)?))
+    }
+}
+
+pub fn from_arrow_schema(schema: &Schema) -> Result<spark::DataType, SparkError> {
+    from_arrow_fields(schema.fields())
+}
+
+pub fn from_arrow_field(field: &Field) -> Result<spark::data_type::StructField, SparkError> {
+    let data_type = match field.metadata().get(CHAR_VARCHAR_METADATA_KEY) {
+        Some(type_string) => parse_char_varchar(type_string)?,
+        None => from_arrow_data_type(field.data_type())?,
+    };
+    Ok(spark::data_type::StructField {
+        name: field.name().clone(),
+        data_type: Some(data_type),
+        nullable: field.is_nullable(),
+        metadata: None,
+    })
+}
+
+pub fn from_arrow_data_type(data_type: &DataType) -> Result<spark::DataType, SparkError> {
+    let kind = match data_type {
+        DataType::Null => Kind::Null(spark::data_type::Null::default()),
+        DataType::Binary | DataType::LargeBinary => {
+            Kind::Binary(spark::data_type::Binary::default())
+        }
+        DataType::Boolean => Kind::Boolean(spark::data_type::Boolean::default()),
+        DataType::Int8 => Kind::Byte(spark::data_type::Byte::default()),
+        DataType::Int16 => Kind::Short(spark::data_type::Short::default()),
+        DataType::Int32 => Kind::Integer(spark::data_type::Integer::default()),
+        DataType::Int64 => Kind::Long(spark::data_type::Long::default()),
+        DataType::Float32 => Kind::Float(spark::data_type::Float::default()),
+        DataType::Float64 => Kind::Double(spark::data_type::Double::default()),
+        DataType::Decimal128(precision, scale) => Kind::Decimal(spark::data_type::Decimal {
+            precision: Some(*precision as i32),
+            scale: Some(*scale as i32),
+            type_variation_reference: 0,
+        }),
+        DataType::Utf8 | DataType::LargeUtf8 => Kind::String(spark::data_type::String::default()),
+        DataType::Date32 => Kind::Date(spark::data_type::Date::default()),
+        DataType::Timestamp(TimeUnit::Microsecond, Some(_)) => {
+            Kind::Timestamp(spark::data_type::Timestamp::default())
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, None) => {
+            Kind::TimestampNtz(spark::data_type::TimestampNtz::default())
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            Kind::CalendarInterval(spark::data_type::CalendarInterval::default())
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            Kind::YearMonthInterval(spark::data_type::YearMonthInterval {
+                start_field: Some(YEAR),
+                end_field: Some(MONTH),
+                type_variation_reference: 0,
+            })
+        }
+        DataType::Duration(TimeUnit::Microsecond) => Kind::DayTimeInterval(spark::data_type::DayTimeInterval {
+            start_field: Some(DAY),
+            end_field: Some(SECOND),
+            type_variation_reference: 0,
+        }),
+        DataType::List(element) | DataType::LargeList(element) => {
+            Kind::Array(Box::new(spark::data_type::Array {
+                element_type: Some(Box::new(from_arrow_data_type(element.data_type())?)),
+                contains_null: element.is_nullable(),
+                type_variation_reference: 0,
+            }))
+        }
+        DataType::Struct(fields) => return from_arrow_fields(fields),
+        DataType::Map(entries, _) => match entries.data_type() {
+            DataType::Struct(fields) if fields.len() == 2 => {
+                Kind::Map(Box::new(spark::data_type::Map {
+                    key_type: Some(Box::new(from_arrow_data_type(fields[0].data_type())?)),
+                    value_type: Some(Box::new(from_arrow_data_type(fields[1].data_type())?)),
+                    value_contains_null: fields[1].is_nullable(),
+                    type_variation_reference: 0,
+                }))
+            }
+            _ => return Err(unsupported(format!("Invalid map entries {:?}", entries))),
+        },
+        // Spark only reads dates as days and timestamps and durations as
+        // microseconds, other units must be cast before sending the data.
+        DataType::Date64 | DataType::Timestamp(_, _) | DataType::Duration(_) => {
+            return Err(unsupported(format!(
+                "Unsupported data type {}, Spark expects Date32 and microsecond timestamps and durations",
+                data_type
+            )))
+        }
+        _ => return Err(unsupported(format!("Unsupported data type {}", data_type))),
+    };
+    Ok(spark::DataType { kind: Some(kind) })
+}
+
+fn from_arrow_fields(fields: &Fields) -> Result<spark::DataType, SparkError> {
+    let fields = fields
+        .iter()
+        .map(|field| from_arrow_field(field))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(spark::DataType {
+        kind: Some(Kind::Struct(spark::data_type::Struct {
+            fields,
+            type_variation_reference: 0,
+        })),
+    })
+}
+
+fn parse_char_varchar(type_string: &str) -> Result<spark::DataType, SparkError> {
+    let parsed = type_string
+        .strip_suffix(')')
+        .and_then(|s| s.split_once('('))
+        .and_then(|(name, length)| Some((name, length.parse::<i32>().ok()?)));
+    let kind = match parsed {
+        Some(("char", length)) => Kind::Char(spark::data_type::Char {
+            length,
+            type_variation_reference: 0,
+        }),
+        Some(("varchar", length)) => Kind::VarChar(spark::data_type::VarChar {
+            length,
+            type_variation_reference: 0,
+        }),
+        _ => return Err(unsupported(format!("Invalid char type {}", type_string))),
+    };
+    Ok(spark::DataType { kind: Some(kind) })
+}
+
+fn unsupported(message: String) -> SparkError {
+    SparkError::NotImplementedYet(NotImplementedYetError(message))
+}
+
+fn invalid(message: String) -> SparkError {
+    SparkError::InvalidArgument(InvalidArgumentError(message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spark_type(kind: Kind) -> spark::DataType {
+        spark::DataType { kind: Some(kind) }
+    }
+
+    fn spark_field(name: &str, kind: Kind, nullable: bool) -> spark::data_type::StructField {
+        spark::data_type::StructField {
+            name: name.to_string(),
+            data_type: Some(spark_type(kind)),
+            nullable,
+            metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_schema_roundtrip() {
+        let schema = spark_type(Kind::Struct(spark::data_type::Struct {
+            fields: vec![
+                spark_field(
+                    "amount",
+                    Kind::Decimal(spark::data_type::Decimal {
+                        precision: Some(38),
+                        scale: Some(18),
+                        type_variation_reference: 0,
+                    }),
+                    true,
+                ),
+                spark_field(
+                    "code",
+                    Kind::Char(spark::data_type::Char {
+                        length: 3,
+                        type_variation_reference: 0,
+                    }),
+                    false,
+                ),
+                spark_field(
+                    "created_at",
+                    Kind::Timestamp(spark::data_type::Timestamp::default()),
+                    true,
+                ),
+                spark_field(
+                    "local_time",
+                    Kind::TimestampNtz(spark::data_type::TimestampNtz::default()),
+                    true,
+                ),
+                spark_field(
+                    "elapsed",
+                    Kind::DayTimeInterval(spark::data_type::DayTimeInterval {
+                        start_field: Some(DAY),
+                        end_field: Some(SECOND),
+                        type_variation_reference: 0,
+                    }),
+                    true,
+                ),
+                spark_field(
+                    "tags",
+                    Kind::Map(Box::new(spark::data_type::Map {
+                        key_type: Some(Box::new(spark_type(Kind::String(
+                            spark::data_type::String::default(),
+                        )))),
+                        value_type: Some(Box::new(spark_type(Kind::Array(Box::new(
+                            spark::data_type::Array {
+                                element_type: Some(Box::new(spark_type(Kind::Long(
+                                    spark::data_type::Long::default(),
+                                )))),
+                                contains_null: false,
+                                type_variation_reference: 0,
+                            },
+                        ))))),
+                        value_contains_null: true,
+                        type_variation_reference: 0,
+                    })),
+                    true,
+                ),
+            ],
+            type_variation_reference: 0,
+        }));
+        let arrow_schema = to_arrow_schema(&schema).unwrap();
+        assert_eq!(
+            arrow_schema.field(0).data_type(),
+            &DataType::Decimal128(38, 18)
+        );
+        assert_eq!(arrow_schema.field(1).data_type(), &DataType::Utf8);
+        assert_eq!(
+            arrow_schema.field(3).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(from_arrow_schema(&arrow_schema).unwrap(), schema);
+        assert_eq!(
+            to_ddl(&schema).unwrap(),
+            "`amount` DECIMAL(38, 18), `code` CHAR(3) NOT NULL, `created_at` TIMESTAMP, \
+             `local_time` TIMESTAMP_NTZ, `elapsed` INTERVAL DAY TO SECOND, \
+             `tags` MAP<STRING, ARRAY<BIGINT>>"
+        );
+    }
+
+    #[test]
+    fn test_udt_converts_to_sql_type() {
+        let udt = spark_type(Kind::Udt(Box::new(spark::data_type::Udt {
+            r#type: "udt".to_string(),
+            jvm_class: Some("org.apache.spark.ml.linalg.VectorUDT".to_string()),
+            python_class: None,
+            serialized_python_class: None,
+            sql_type: Some(Box::new(spark_type(Kind::Double(
+                spark::data_type::Double::default(),
+            )))),
+        })));
+        assert_eq!(to_arrow_data_type(&udt).unwrap(), DataType::Float64);
+    }
+
+    #[test]
+    fn test_empty_schema_ddl() {
+        let schema = from_arrow_schema(&Schema::empty()).unwrap();
+        assert_eq!(to_ddl(&schema).unwrap(), "STRUCT<>");
+    }
+
+    #[test]
+    fn test_units_unread_by_spark_are_rejected() {
+        for data_type in [
+            DataType::Date64,
+            DataType::Timestamp(TimeUnit::Nanosecond, Some(TIME_ZONE.into())),
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            DataType::Duration(TimeUnit::Second),
+        ] {
+            assert!(matches!(
+                from_arrow_data_type(&data_type),
+                Err(SparkError::NotImplementedYet(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_decimal_is_rejected() {
+        let decimal = spark_type(Kind::Decimal(spark::data_type::Decimal {
+            precision: Some(300),
+            scale: Some(2),
+            type_variation_reference: 0,
+        }));
+        assert!(matches!(
+            to_arrow_data_type(&decimal),
+            Err(SparkError::InvalidArgument(_))
+        ));
+    }
+}
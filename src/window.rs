@@ -0,0 +1,118 @@
+//! Window specifications, applied to window and aggregate functions with
+//! `Column::over`.
+use crate::column::Column;
+use crate::spark;
+use crate::spark::expression::window::window_frame::{self, frame_boundary, FrameType};
+use crate::spark::expression::window::WindowFrame;
+use crate::spark::expression::ExprType;
+use crate::spark::expression::Literal;
+
+/// Boundary of a window frame, relative to the current row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// The first row of the partition as lower boundary, the last one as
+    /// upper boundary.
+    Unbounded,
+    CurrentRow,
+    /// Rows before the current row when negative, after it when positive.
+    /// In a range frame, the offset applies to the value of the ordering
+    /// column.
+    Offset(i64),
+}
+
+impl From<Boundary> for window_frame::FrameBoundary {
+    fn from(boundary: Boundary) -> Self {
+        let boundary = match boundary {
+            Boundary::Unbounded => frame_boundary::Boundary::Unbounded(true),
+            Boundary::CurrentRow => frame_boundary::Boundary::CurrentRow(true),
+            // Spark only accepts integer offsets in row frames
+            Boundary::Offset(offset) => frame_boundary::Boundary::Value(Box::new(
+                Column::from(match i32::try_from(offset) {
+                    Ok(offset) => Literal::from(offset),
+                    Err(_) => Literal::from(offset),
+                })
+                .into(),
+            )),
+        };
+        window_frame::FrameBoundary {
+            boundary: Some(boundary),
+        }
+    }
+}
+
+/// Entry point to build a `WindowSpec`, e.g.
+/// `Window::partition_by(vec![col("department")]).order_by(vec![col("salary").desc()])`.
+pub struct Window;
+
+impl Window {
+    pub fn partition_by(cols: Vec<Column>) -> WindowSpec {
+        WindowSpec::default().partition_by(cols)
+    }
+
+    pub fn order_by(cols: Vec<Column>) -> WindowSpec {
+        WindowSpec::default().order_by(cols)
+    }
+
+    pub fn rows_between(start: Boundary, end: Boundary) -> WindowSpec {
+        WindowSpec::default().rows_between(start, end)
+    }
+
+    pub fn range_between(start: Boundary, end: Boundary) -> WindowSpec {
+        WindowSpec::default().range_between(start, end)
+    }
+}
+
+/// Partitioning, ordering and frame of a window. Without a frame, Spark
+/// uses the rows from the start of the partition to the current row when
+/// the window is ordered, and the whole partition otherwise.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowSpec {
+    partition_spec: Vec<spark::Expression>,
+    order_spec: Vec<spark::expression::SortOrder>,
+    frame_spec: Option<WindowFrame>,
+}
+
+impl WindowSpec {
+    pub fn partition_by(mut self, cols: Vec<Column>) -> Self {
+        self.partition_spec = cols.into_iter().map(|col| col.expression).collect();
+        self
+    }
+
+    /// Orders the rows of each partition by `cols`, in ascending order unless
+    /// given as sort orders with e.g. `Column::desc`.
+    pub fn order_by(mut self, cols: Vec<Column>) -> Self {
+        self.order_spec = cols.into_iter().map(Column::into_sort_order).collect();
+        self
+    }
+
+    /// Frame of the rows between `start` and `end`, counted in rows from the
+    /// current row.
+    pub fn rows_between(self, start: Boundary, end: Boundary) -> Self {
+        self.frame(FrameType::Row, start, end)
+    }
+
+    /// Frame of the rows whose ordering value is between the one of the
+    /// current row plus `start` and plus `end`. The window must be ordered by
+    /// a single column when the boundaries are offsets.
+    pub fn range_between(self, start: Boundary, end: Boundary) -> Self {
+        self.frame(FrameType::Range, start, end)
+    }
+
+    fn frame(mut self, frame_type: FrameType, start: Boundary, end: Boundary) -> Self {
+        self.frame_spec = Some(WindowFrame {
+            frame_type: frame_type.into(),
+            lower: Some(Box::new(start.into())),
+            upper: Some(Box::new(end.into())),
+        });
+        self
+    }
+
+    pub(crate) fn apply(&self, function: Column) -> Column {
+        Column::from(ExprType::Window(Box::new(spark::expression::Window {
+            window_function: Some(Box::new(function.expression)),
+            partition_spec: self.partition_spec.clone(),
+            order_spec: self.order_spec.clone(),
+            frame_spec: self.frame_spec.clone().map(Box::new),
+        })))
+    }
+}
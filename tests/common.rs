@@ -1,3 +1,6 @@
+#![allow(dead_code)]
+
+use rand::Rng;
 use spark_connect_rust::RemoteSparkSession;
 use spark_connect_rust::{dataframe::DataFrame, error::SparkSessionCreationError};
 use std::collections::HashMap;
@@ -30,3 +33,12 @@ pub async fn read_dataframe_from_path(
         .options(options)
         .load(vec![path])
 }
+
+/// Random lowercase alphanumeric suffix, to keep names unique on the shared server.
+pub fn random_string() -> String {
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(8)
+        .map(|c| char::from(c).to_ascii_lowercase())
+        .collect()
+}
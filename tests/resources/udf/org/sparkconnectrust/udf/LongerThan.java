@@ -0,0 +1,10 @@
+package org.sparkconnectrust.udf;
+
+import org.apache.spark.sql.api.java.UDF2;
+
+public class LongerThan implements UDF2<String, Integer, Boolean> {
+    @Override
+    public Boolean call(String value, Integer length) {
+        return value != null && value.length() > length;
+    }
+}
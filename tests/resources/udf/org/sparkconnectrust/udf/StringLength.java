@@ -0,0 +1,10 @@
+package org.sparkconnectrust.udf;
+
+import org.apache.spark.sql.api.java.UDF1;
+
+public class StringLength implements UDF1<String, Integer> {
+    @Override
+    public Integer call(String value) {
+        return value == null ? null : value.length();
+    }
+}
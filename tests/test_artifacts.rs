@@ -0,0 +1,36 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::error::SparkError;
+use std::env;
+use std::error::Error;
+use std::fs;
+
+#[tokio::test]
+async fn test_add_file_uploads_local_file() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let path = env::temp_dir().join("spark_connect_rust_add_file.txt");
+    fs::write(&path, "Michael\nAndy\n")?;
+    session.add_file(path.to_string_lossy().to_string()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_artifacts_rejects_unsupported_files() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let result = session
+        .add_artifacts(vec!["/tmp/employees.csv".to_string()])
+        .await;
+    assert!(matches!(result, Err(SparkError::InvalidArgument(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_artifact_status_reports_missing_artifacts() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let statuses = session
+        .artifact_status(vec!["cache/missing".to_string()])
+        .await?;
+    assert_eq!(statuses.get("cache/missing"), Some(&false));
+    Ok(())
+}
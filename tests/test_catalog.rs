@@ -0,0 +1,105 @@
+mod common;
+mod test_util;
+use common::{new_session, random_string};
+use std::collections::HashMap;
+use std::error::Error;
+
+#[tokio::test]
+async fn test_current_database_and_list_databases() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let catalog = session.catalog();
+    assert_eq!(catalog.current_database().await?, "default");
+    let databases = catalog.list_databases(Some("def*".to_string())).await?;
+    assert_eq!(
+        databases
+            .iter()
+            .map(|db| db.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["default"]
+    );
+    catalog.set_current_database("default".to_string()).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_external_table_and_list_columns() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let catalog = session.clone().catalog();
+    let table_name = format!("catalog_employees_{}", random_string());
+    catalog
+        .create_external_table(
+            table_name.clone(),
+            "/opt/spark/examples/src/main/resources/employees.json".to_string(),
+            Some("json".to_string()),
+            None,
+            HashMap::new(),
+        )
+        .await?;
+    assert!(catalog.table_exists(table_name.clone(), None).await?);
+    let tables = catalog.list_tables(None, Some(table_name.clone())).await?;
+    assert_eq!(tables.len(), 1);
+    assert_eq!(tables[0].table_type, "EXTERNAL");
+    assert!(!tables[0].is_temporary);
+    let columns = catalog.list_columns(table_name.clone(), None).await?;
+    assert_eq!(
+        columns
+            .iter()
+            .map(|column| (column.name.as_str(), column.data_type.as_str()))
+            .collect::<Vec<_>>(),
+        vec![("name", "string"), ("salary", "bigint")]
+    );
+    session
+        .sql(format!("DROP TABLE {}", table_name))
+        .collect()
+        .await?;
+    assert!(!catalog.table_exists(table_name, None).await?);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cache_table_and_drop_temp_view() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    session
+        .clone()
+        .sql("CREATE OR REPLACE TEMP VIEW catalog_numbers AS SELECT * FROM range(10)".to_string())
+        .collect()
+        .await?;
+    let catalog = session.catalog();
+    assert!(!catalog.is_cached("catalog_numbers".to_string()).await?);
+    catalog
+        .cache_table("catalog_numbers".to_string(), None)
+        .await?;
+    assert!(catalog.is_cached("catalog_numbers".to_string()).await?);
+    catalog.uncache_table("catalog_numbers".to_string()).await?;
+    assert!(!catalog.is_cached("catalog_numbers".to_string()).await?);
+    catalog.clear_cache().await?;
+    assert!(
+        catalog
+            .drop_temp_view("catalog_numbers".to_string())
+            .await?
+    );
+    assert!(
+        !catalog
+            .drop_temp_view("catalog_numbers".to_string())
+            .await?
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_function_exists_and_list_functions() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let catalog = session.catalog();
+    assert!(catalog.function_exists("upper".to_string(), None).await?);
+    assert!(
+        !catalog
+            .function_exists("not_a_function".to_string(), None)
+            .await?
+    );
+    let functions = catalog
+        .list_functions(None, Some("upper".to_string()))
+        .await?;
+    assert_eq!(functions.len(), 1);
+    assert_eq!(functions[0].name, "upper");
+    Ok(())
+}
@@ -0,0 +1,161 @@
+mod common;
+mod test_util;
+use arrow::array::{ArrayRef, Int64Array, StringArray};
+use arrow::record_batch::RecordBatch;
+use arrow_ipc::writer::StreamWriter;
+use common::new_session;
+use prost::Message;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use spark_connect_rust::spark;
+use spark_connect_rust::types::{from_arrow_schema, to_ddl};
+use spark_connect_rust::RemoteSparkSession;
+use std::error::Error;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn test_create_dataframe_from_batches() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let batch = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+        (
+            "name",
+            Arc::new(StringArray::from(vec!["Michael", "Andy"])) as ArrayRef,
+        ),
+    ])?;
+    let rows = session
+        .create_dataframe(vec![batch])
+        .await?
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+----+---------+",
+            "| id | name    |",
+            "+----+---------+",
+            "| 1  | Michael |",
+            "| 2  | Andy    |",
+            "+----+---------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct Department {
+    name: String,
+    department: String,
+}
+
+#[tokio::test]
+async fn test_create_dataframe_from_rows() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let departments = vec![
+        Department {
+            name: "Michael".to_string(),
+            department: "Sales".to_string(),
+        },
+        Department {
+            name: "Berta".to_string(),
+            department: "Engineering".to_string(),
+        },
+    ];
+    let rows = session
+        .create_dataframe_from_rows(&departments)
+        .await?
+        .select_expr(vec!["upper(department) AS department".to_string()])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------------+",
+            "| department  |",
+            "+-------------+",
+            "| SALES       |",
+            "| ENGINEERING |",
+            "+-------------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_dataframe_from_no_rows_is_empty() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows = session
+        .create_dataframe_from_rows::<Department>(&[])
+        .await?
+        .select_expr(vec!["count(*) AS rows".to_string()])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec!["+------+", "| rows |", "+------+", "| 0    |", "+------+",],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_dataframe_above_threshold_uses_cached_relation() -> Result<(), Box<dyn Error>>
+{
+    let session = new_session().await?;
+    session.set_local_relation_cache_threshold(0);
+    let ids: Vec<i64> = (0..100_000).collect();
+    let batch =
+        RecordBatch::try_from_iter(vec![("id", Arc::new(Int64Array::from(ids)) as ArrayRef)])?;
+    let name = cache_artifact_name(&batch)?;
+    assert!(!is_cached(&session, &name).await?);
+    let first = session
+        .clone()
+        .create_dataframe(vec![batch.clone()])
+        .await?
+        .select_expr(vec![
+            "count(*) AS rows".to_string(),
+            "sum(id) AS total".to_string(),
+        ])
+        .collect()
+        .await?;
+    // Uploads only happen for data missing on the server, so the second
+    // DataFrame reuses the artifact uploaded by the first one.
+    assert!(is_cached(&session, &name).await?);
+    let second = session
+        .create_dataframe(vec![batch])
+        .await?
+        .select_expr(vec![
+            "count(*) AS rows".to_string(),
+            "sum(id) AS total".to_string(),
+        ])
+        .collect()
+        .await?;
+    let expected = vec![
+        "+--------+------------+",
+        "| rows   | total      |",
+        "+--------+------------+",
+        "| 100000 | 4999950000 |",
+        "+--------+------------+",
+    ];
+    assert_batches_eq!(expected.clone(), &first);
+    assert_batches_eq!(expected, &second);
+    Ok(())
+}
+
+/// Name of the cache artifact `create_dataframe` uploads for `batch`.
+fn cache_artifact_name(batch: &RecordBatch) -> Result<String, Box<dyn Error>> {
+    let mut writer = StreamWriter::try_new(Vec::new(), &batch.schema())?;
+    writer.write(batch)?;
+    let relation = spark::LocalRelation {
+        data: Some(writer.into_inner()?),
+        schema: Some(to_ddl(&from_arrow_schema(&batch.schema())?)?),
+    };
+    Ok(format!(
+        "cache/{}",
+        hex::encode(Sha256::digest(relation.encode_to_vec()))
+    ))
+}
+
+async fn is_cached(session: &RemoteSparkSession, name: &str) -> Result<bool, Box<dyn Error>> {
+    let statuses = session.artifact_status(vec![name.to_string()]).await?;
+    Ok(statuses.get(name).copied().unwrap_or(false))
+}
@@ -2,11 +2,15 @@ mod common;
 mod test_util;
 
 use crate::common::new_session;
-use rand::Rng;
-use spark_connect_rust::dataframe::DataFrame;
+use futures::TryStreamExt;
+use serde::Deserialize;
+use spark_connect_rust::column::{col, expr, Column};
+use spark_connect_rust::dataframe::{broadcast, DataFrame};
+use spark_connect_rust::error::SparkError;
+use spark_connect_rust::spark::expression::Literal;
+use spark_connect_rust::spark::join::JoinType;
 use spark_connect_rust::spark::write_operation::SaveMode;
-use spark_connect_rust::{error::SparkError, spark};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::fs;
 
@@ -64,6 +68,81 @@ async fn test_select_wrong_expr_error() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_filter_and_select_columns_works() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let rows = dataframe
+        .filter(expr("salary > 3500"))
+        .select(vec![col("name"), expr("salary * 2").alias("double_salary")])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+---------------+",
+            "| name  | double_salary |",
+            "+-------+---------------+",
+            "| Andy  | 9000          |",
+            "| Berta | 8000          |",
+            "+-------+---------------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Employee {
+    name: String,
+    salary: i64,
+}
+
+#[tokio::test]
+async fn test_collect_as_works() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let employees: Vec<Employee> = dataframe.collect_as().await?;
+    assert_eq!(employees.len(), 4);
+    assert_eq!(
+        employees[0],
+        Employee {
+            name: "Michael".to_string(),
+            salary: 3000
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_to_stream_as_works() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let names: Vec<(String,)> = dataframe
+        .select(vec!["name".to_string()])
+        .to_stream_as()
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(
+        names,
+        vec![
+            ("Michael".to_string(),),
+            ("Andy".to_string(),),
+            ("Justin".to_string(),),
+            ("Berta".to_string(),),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_collect_as_type_mismatch_fails() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let result = dataframe.collect_as::<(String, String)>().await;
+    match result {
+        Err(SparkError::DeserializationFailed(err)) => assert!(err.to_string().contains("salary")),
+        _ => panic!("Expected a deserialization error"),
+    }
+    Ok(())
+}
+
 async fn write_to_temp_folder(
     df: &DataFrame,
     dir: &String,
@@ -80,22 +159,14 @@ async fn write_to_temp_folder(
     };
     writer
         .options(options)
-        .save(Some(spark::write_operation::SaveType::Path(
-            file_path.clone(),
-        )))
+        .save(Some(file_path.clone()))
         .await?;
     Ok(file_path.clone())
 }
 
 fn random_path() -> String {
     // Short path to be used in the Spark current working directory /opt/spark/work-dir
-    let rng = rand::thread_rng();
-    let random_string: String = rng
-        .sample_iter(&rand::distributions::Alphanumeric)
-        .take(8)
-        .map(char::from)
-        .collect();
-    let temp_dir = format!("tmp_{}", random_string);
+    let temp_dir = format!("tmp_{}", common::random_string());
     fs::create_dir(&temp_dir).expect("Failed to create temporary directory");
     temp_dir
 }
@@ -177,3 +248,248 @@ async fn test_write_works() -> Result<(), Box<dyn Error>> {
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn test_save_as_table_and_insert_into_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let input_df = session.clone().sql(
+        "SELECT * FROM json.`/opt/spark/examples/src/main/resources/employees.json`".to_owned(),
+    );
+    let table_name = format!("employees_{}", common::random_string());
+    input_df
+        .write()
+        .format("parquet".to_string())
+        .bucket_by(2, vec!["name".to_string()])
+        .sort_by(vec!["salary".to_string()])
+        .save_as_table(table_name.clone())
+        .await?;
+    input_df
+        .write()
+        .insert_into(table_name.clone(), false)
+        .await?;
+    let rows = session
+        .clone()
+        .sql(format!("SELECT count(*) AS count FROM {}", table_name))
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 8     |",
+            "+-------+",
+        ],
+        &rows
+    );
+    input_df
+        .write()
+        .insert_into(table_name.clone(), true)
+        .await?;
+    let rows = session
+        .clone()
+        .sql(format!("SELECT count(*) AS count FROM {}", table_name))
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 4     |",
+            "+-------+",
+        ],
+        &rows
+    );
+    session
+        .sql(format!("DROP TABLE {}", table_name))
+        .collect()
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_partition_by_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let input_df = session.clone().sql(
+        "SELECT * FROM json.`/opt/spark/examples/src/main/resources/employees.json`".to_owned(),
+    );
+    let path = format!("{}/employees", random_path());
+    input_df
+        .write()
+        .format("parquet".to_string())
+        .partition_by(vec!["salary".to_string()])
+        .save(Some(path.clone()))
+        .await?;
+    let rows = common::read_dataframe_from_path(
+        session,
+        "parquet".to_string(),
+        HashMap::new(),
+        format!("{}/salary=4500", path),
+    )
+    .await
+    .collect()
+    .await?;
+    assert_batches_eq!(
+        vec!["+------+", "| name |", "+------+", "| Andy |", "+------+",],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_write_to_create_and_append_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let input_df = session.clone().sql(
+        "SELECT * FROM json.`/opt/spark/examples/src/main/resources/employees.json`".to_owned(),
+    );
+    let table_name = format!("employees_v2_{}", common::random_string());
+    input_df
+        .write_to(table_name.clone())
+        .using("parquet".to_string())
+        .table_property("owner".to_string(), "hr".to_string())
+        .partitioned_by(vec![col("salary")])
+        .create()
+        .await?;
+    input_df.write_to(table_name.clone()).append().await?;
+    let rows = session
+        .clone()
+        .sql(format!("SELECT count(*) AS count FROM {}", table_name))
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+",
+            "| count |",
+            "+-------+",
+            "| 8     |",
+            "+-------+",
+        ],
+        &rows
+    );
+    let result = input_df.write_to(table_name.clone()).create().await;
+    assert!(result.is_err());
+    session
+        .sql(format!("DROP TABLE {}", table_name))
+        .collect()
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_alias_allows_qualified_references() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let rows = dataframe
+        .alias("employees")
+        .filter(expr("employees.salary >= 4000"))
+        .select(vec![col("employees.name")])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+",
+            "| name  |",
+            "+-------+",
+            "| Andy  |",
+            "| Berta |",
+            "+-------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_self_join_with_aliases_works() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let mut pairs: Vec<(String, String)> = dataframe
+        .alias("a")
+        .join(
+            &dataframe.alias("b"),
+            expr("a.salary = b.salary + 500"),
+            JoinType::Inner,
+        )
+        .select(vec![col("a.name"), col("b.name")])
+        .collect_as()
+        .await?;
+    pairs.sort();
+    assert_eq!(
+        pairs,
+        vec![
+            ("Andy".to_string(), "Berta".to_string()),
+            ("Berta".to_string(), "Justin".to_string()),
+            ("Justin".to_string(), "Michael".to_string()),
+        ]
+    );
+    let count: Vec<(i64,)> = dataframe
+        .alias("a")
+        .cross_join(&dataframe.alias("b"))
+        .filter(expr("a.name <> b.name"))
+        .select(vec![expr("count(*)")])
+        .collect_as()
+        .await?;
+    assert_eq!(count, vec![(12,)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_hints_work() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let partitions: HashSet<i32> = session
+        .clone()
+        .range(0, 100, 1, Some(1))
+        .hint("repartition", vec![Column::from(Literal::from(3))])
+        .select(vec![expr("spark_partition_id()")])
+        .collect_as::<(i32,)>()
+        .await?
+        .into_iter()
+        .map(|(partition,)| partition)
+        .collect();
+    assert_eq!(partitions.len(), 3);
+    let rows = broadcast(&session.range(0, 3, 1, None)).collect().await?;
+    assert_batches_eq!(
+        vec!["+----+", "| id |", "+----+", "| 0  |", "| 1  |", "| 2  |", "+----+",],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unpivot_and_melt_work() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let revenues = session
+        .sql("SELECT * FROM VALUES (1, 11, 12), (2, 21, 22) AS revenues(id, q1, q2)".to_owned());
+    let rows = revenues
+        .unpivot(vec![col("id")], None, "quarter", "revenue")
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+----+---------+---------+",
+            "| id | quarter | revenue |",
+            "+----+---------+---------+",
+            "| 1  | q1      | 11      |",
+            "| 1  | q2      | 12      |",
+            "| 2  | q1      | 21      |",
+            "| 2  | q2      | 22      |",
+            "+----+---------+---------+",
+        ],
+        &rows
+    );
+    let rows = revenues
+        .melt(vec![col("id")], Some(vec![col("q2")]), "quarter", "revenue")
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+----+---------+---------+",
+            "| id | quarter | revenue |",
+            "+----+---------+---------+",
+            "| 1  | q2      | 12      |",
+            "| 2  | q2      | 22      |",
+            "+----+---------+---------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
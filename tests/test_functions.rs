@@ -0,0 +1,233 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::column::{col, expr, Column};
+use spark_connect_rust::functions::*;
+use spark_connect_rust::spark::expression::Literal;
+use std::collections::HashMap;
+use std::error::Error;
+
+#[tokio::test]
+async fn test_string_and_math_functions() -> Result<(), Box<dyn Error>> {
+    let employees = common::create_employees_dataframe().await?;
+    let rows: Vec<(String, String, i32, String, f64, i64)> = employees
+        .filter(expr("name = 'Andy'"))
+        .select(vec![
+            upper("name"),
+            concat_ws("-", vec![col("name"), col("name")]),
+            length("name"),
+            lpad(substring("name", 2, 2), 4, "*"),
+            round(sqrt("salary"), 2),
+            abs(expr("-salary")),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(
+            "ANDY".to_string(),
+            "Andy-Andy".to_string(),
+            4,
+            "**nd".to_string(),
+            67.08,
+            4500,
+        )]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_date_and_time_functions() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows: Vec<(i32, i32, String, i32, String)> = session
+        .sql("SELECT '2023-01-31' AS day".to_owned())
+        .select(vec![
+            year(to_date("day", None)),
+            month(add_months(to_date("day", None), 1)),
+            date_format(
+                date_add(to_date("day", Some("yyyy-MM-dd")), 1),
+                "dd/MM/yyyy",
+            ),
+            datediff(to_date(Literal::from("2023-03-01"), None), "day"),
+            date_format(
+                date_trunc("month", to_timestamp("day", None)),
+                "yyyy-MM-dd HH:mm",
+            ),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(
+            2023,
+            2,
+            "01/02/2023".to_string(),
+            29,
+            "2023-01-01 00:00".to_string()
+        )]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_collection_and_json_functions() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session
+        .sql("SELECT array(3, 1, 2, 1) AS numbers, '{\"a\": {\"b\": 7}}' AS json".to_owned());
+    let rows: Vec<(i32, bool, String, i32, String, String)> = dataframe
+        .select(vec![
+            size("numbers"),
+            array_contains("numbers", Literal::from(2)),
+            array_join(sort_array(array_distinct("numbers"), false), ","),
+            element_at("numbers", Literal::from(1)),
+            get_json_object("json", "$.a.b"),
+            to_json(from_json("json", "a STRUCT<b: INT>", HashMap::new())),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(
+            4,
+            true,
+            "3,2,1".to_string(),
+            3,
+            "7".to_string(),
+            "{\"a\":{\"b\":7}}".to_string()
+        )]
+    );
+    let rows: Vec<(i32,)> = dataframe
+        .select(vec![explode("numbers")])
+        .collect_as()
+        .await?;
+    assert_eq!(rows, vec![(3,), (1,), (2,), (1,)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_aggregate_functions() -> Result<(), Box<dyn Error>> {
+    let employees = common::create_employees_dataframe().await?;
+    let rows: Vec<(i64, i64, i64, f64, i64, String)> = employees
+        .select(vec![
+            count(Literal::from(1)),
+            count_distinct(vec!["name"]),
+            sum("salary"),
+            avg("salary"),
+            max("salary"),
+            min_by("name", "salary"),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(4, 4, 15000, 3750.0, 4500, "Michael".to_string())]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_conditional_functions() -> Result<(), Box<dyn Error>> {
+    let employees = common::create_employees_dataframe().await?;
+    let rows: Vec<(String, String)> = employees
+        .select(vec![
+            coalesce(vec![expr("NULL"), col("name")]),
+            when(expr("salary >= 4000"), Literal::from("high"))
+                .when(expr("salary >= 3500"), Literal::from("mid"))
+                .otherwise(Literal::from("low")),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![
+            ("Michael".to_string(), "low".to_string()),
+            ("Andy".to_string(), "high".to_string()),
+            ("Justin".to_string(), "mid".to_string()),
+            ("Berta".to_string(), "high".to_string()),
+        ]
+    );
+    Ok(())
+}
+
+type ArrayResults = (Vec<i32>, Vec<i32>, bool, bool, Vec<i32>, Vec<Vec<i32>>);
+
+#[tokio::test]
+async fn test_higher_order_functions_on_arrays() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session
+        .sql("SELECT array(1, 2, 3) AS numbers, array(array(1, 2), array(3)) AS matrix".to_owned());
+    let two = || Column::from(Literal::from(2));
+    let rows: Vec<ArrayResults> = dataframe
+        .select(vec![
+            transform("numbers", |x| greatest(vec![x, two()])),
+            filter("numbers", |x| array_contains(array(vec![two()]), x)),
+            exists("numbers", |x| array_contains(array(vec![two()]), x)),
+            forall("numbers", isnull),
+            zip_with("numbers", array(vec![two()]), |x, y| coalesce(vec![y, x])),
+            transform("matrix", |row| {
+                transform(row.clone(), |x| greatest(vec![x, size(row)]))
+            }),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(
+            vec![2, 2, 3],
+            vec![2],
+            true,
+            false,
+            vec![2, 2, 3],
+            vec![vec![2, 2], vec![3]],
+        )]
+    );
+    let rows: Vec<(i32, f64)> = dataframe
+        .select(vec![
+            aggregate("numbers", Literal::from(0), |acc, x| greatest(vec![acc, x])),
+            aggregate_with_finish(
+                "numbers",
+                Literal::from(0),
+                |acc, x| greatest(vec![acc, x]),
+                |acc| pow(acc, two()),
+            ),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(rows, vec![(3, 9.0)]);
+    Ok(())
+}
+
+type Scores = HashMap<String, i32>;
+
+#[tokio::test]
+async fn test_higher_order_functions_on_maps() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session.sql("SELECT map('a', 1, 'b', 2) AS scores".to_owned());
+    let rows: Vec<(Scores, Scores, Scores)> = dataframe
+        .select(vec![
+            map_filter("scores", |key, _| {
+                array_contains(array(vec![Literal::from("a")]), key)
+            }),
+            transform_keys("scores", |key, _| upper(key)),
+            transform_values("scores", |_, value| {
+                greatest(vec![value, Column::from(Literal::from(2))])
+            }),
+        ])
+        .collect_as()
+        .await?;
+    let map = |entries: &[(&str, i32)]| -> Scores {
+        entries
+            .iter()
+            .map(|(key, value)| (key.to_string(), *value))
+            .collect()
+    };
+    assert_eq!(
+        rows,
+        vec![(
+            map(&[("a", 1)]),
+            map(&[("A", 1), ("B", 2)]),
+            map(&[("a", 2), ("b", 2)]),
+        )]
+    );
+    Ok(())
+}
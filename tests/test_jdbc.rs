@@ -0,0 +1,115 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::options::JdbcPartitioning;
+use spark_connect_rust::RemoteSparkSession;
+use std::collections::HashMap;
+use std::error::Error;
+use std::rc::Rc;
+
+// Embedded Derby ships with the Spark distribution, the in-memory database
+// lives in the JVM of the Spark Connect server.
+const DERBY_URL: &str = "jdbc:derby:memory:spark_connect_rust;create=true";
+
+fn derby_properties() -> HashMap<String, String> {
+    vec![(
+        "driver".to_string(),
+        "org.apache.derby.jdbc.EmbeddedDriver".to_string(),
+    )]
+    .into_iter()
+    .collect()
+}
+
+async fn create_employees_table(
+    session: Rc<RemoteSparkSession>,
+    table: &str,
+) -> Result<(), Box<dyn Error>> {
+    session
+        .sql(
+            "SELECT name, salary FROM json.`/opt/spark/examples/src/main/resources/employees.json`"
+                .to_owned(),
+        )
+        .write()
+        .format("jdbc".to_string())
+        .options(derby_properties())
+        .options(
+            vec![
+                ("url".to_string(), DERBY_URL.to_string()),
+                ("dbtable".to_string(), table.to_string()),
+            ]
+            .into_iter()
+            .collect::<HashMap<_, _>>(),
+        )
+        .mode(spark_connect_rust::spark::write_operation::SaveMode::Overwrite)
+        .save(None)
+        .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_jdbc_with_partitioning_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    create_employees_table(session.clone(), "employees_partitioned").await?;
+    let rows = session
+        .read()
+        .jdbc_with_partitioning(
+            DERBY_URL.to_string(),
+            "employees_partitioned".to_string(),
+            JdbcPartitioning {
+                column: "salary".to_string(),
+                lower_bound: "3000".to_string(),
+                upper_bound: "4500".to_string(),
+                num_partitions: 3,
+            },
+            derby_properties(),
+        )
+        .select_expr(vec![
+            "count(*) AS count".to_string(),
+            "sum(salary) AS total".to_string(),
+        ])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+-------+",
+            "| count | total |",
+            "+-------+-------+",
+            "| 4     | 15000 |",
+            "+-------+-------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_jdbc_with_predicates_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    create_employees_table(session.clone(), "employees_predicates").await?;
+    let rows = session
+        .read()
+        .jdbc_with_predicates(
+            DERBY_URL.to_string(),
+            "employees_predicates".to_string(),
+            // Spark creates the columns as quoted, case-sensitive, identifiers
+            vec![
+                "\"salary\" < 3500".to_string(),
+                "\"salary\" > 4000".to_string(),
+            ],
+            derby_properties(),
+        )
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+---------+--------+",
+            "| name    | salary |",
+            "+---------+--------+",
+            "| Michael | 3000   |",
+            "| Andy    | 4500   |",
+            "+---------+--------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
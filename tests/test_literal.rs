@@ -0,0 +1,101 @@
+mod common;
+mod test_util;
+use chrono::{NaiveDate, TimeZone, Utc};
+use common::new_session;
+use spark_connect_rust::functions::*;
+use spark_connect_rust::literal::{null, struct_literal, Decimal, SparkDataType};
+use std::collections::HashMap;
+use std::error::Error;
+
+type Scalars = (i8, i16, i32, i64, f32, f64, bool, String, i32);
+
+#[tokio::test]
+async fn test_scalar_literals() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows: Vec<Scalars> = session
+        .range(0, 1, 1, None)
+        .select(vec![
+            lit(1i8),
+            lit(2i16),
+            lit(3),
+            lit(4i64),
+            lit(1.5f32),
+            lit(2.5),
+            lit(true),
+            lit("spark"),
+            length(lit(vec![1u8, 2, 3])),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(1, 2, 3, 4, 1.5, 2.5, true, "spark".to_string(), 3)]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_decimal_and_temporal_literals() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let day = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap();
+    let rows: Vec<(String, String, String, i32, i64)> = session
+        .range(0, 1, 1, None)
+        .select(vec![
+            lit(Decimal::new(12345, 5, 2)?),
+            date_format(lit(day), "yyyy-MM-dd"),
+            date_format(
+                lit(day.and_hms_opt(12, 30, 0).unwrap()),
+                "yyyy-MM-dd HH:mm:ss",
+            ),
+            year(lit(Utc.with_ymd_and_hms(2023, 6, 15, 12, 0, 0).unwrap())),
+            lit(chrono::Duration::hours(36)),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(
+            "123.45".to_string(),
+            "2023-01-31".to_string(),
+            "2023-01-31 12:30:00".to_string(),
+            2023,
+            36 * 3600 * 1_000_000,
+        )]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_complex_and_null_literals() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let scores: HashMap<&str, i64> = vec![("a", 1), ("b", 2)].into_iter().collect();
+    let rows: Vec<(i32, i32, String, i64, String, bool, bool)> = session
+        .range(0, 1, 1, None)
+        .select(vec![
+            size(lit(vec![1, 2, 3])),
+            size(lit(Vec::<String>::new())),
+            element_at(lit(vec!["a", "b"]), lit(2)),
+            element_at(lit(scores), lit("b")),
+            to_json(lit(struct_literal(vec![
+                ("x", 1.into()),
+                ("label", "origin".into()),
+            ]))),
+            isnull(lit(None::<i32>)),
+            isnull(lit(null(f64::data_type()))),
+        ])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![(
+            3,
+            0,
+            "b".to_string(),
+            2,
+            "{\"x\":1,\"label\":\"origin\"}".to_string(),
+            true,
+            true,
+        )]
+    );
+    Ok(())
+}
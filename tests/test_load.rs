@@ -1,6 +1,11 @@
 mod common;
 mod test_util;
+use arrow::datatypes::{DataType, Field, Schema};
 use common::new_session;
+use spark_connect_rust::error::SparkError;
+use spark_connect_rust::options::{CsvOptions, ParseMode};
+use spark_connect_rust::spark;
+use spark_connect_rust::types::from_arrow_schema;
 use std::error::Error;
 
 #[tokio::test]
@@ -31,3 +36,188 @@ async fn test_load_works() -> Result<(), Box<dyn Error>> {
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn test_csv_with_typed_options_and_ddl_schema_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows = session
+        .read()
+        .options(CsvOptions {
+            header: Some(true),
+            sep: Some(";".to_string()),
+            mode: Some(ParseMode::FailFast),
+            ..Default::default()
+        })
+        .schema("name STRING, age INT, job STRING")?
+        .csv(vec![
+            "/opt/spark/examples/src/main/resources/people.csv".to_string()
+        ])
+        .select_expr(vec!["name".to_string(), "age + 1 AS next_age".to_string()])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+----------+",
+            "| name  | next_age |",
+            "+-------+----------+",
+            "| Jorge | 31       |",
+            "| Bob   | 33       |",
+            "+-------+----------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_with_data_type_schema_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let schema = from_arrow_schema(&Schema::new(vec![Field::new("name", DataType::Utf8, true)]))?;
+    let rows = session
+        .read()
+        .schema(schema)?
+        .json(vec![
+            "/opt/spark/examples/src/main/resources/employees.json".to_string(),
+        ])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+---------+",
+            "| name    |",
+            "+---------+",
+            "| Michael |",
+            "| Andy    |",
+            "| Justin  |",
+            "| Berta   |",
+            "+---------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_schema_without_kind_fails() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let result = session.read().schema(spark::DataType { kind: None });
+    assert!(matches!(result, Err(SparkError::NotImplementedYet(_))));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cleared_schema_is_inferred() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows = session
+        .read()
+        .schema("name STRING")?
+        .schema(None)?
+        .json(vec![
+            "/opt/spark/examples/src/main/resources/employees.json".to_string(),
+        ])
+        .collect()
+        .await?;
+    assert_eq!(rows[0].num_columns(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_text_with_option_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows = session
+        .read()
+        .option("lineSep".to_string(), ",".to_string())
+        .text(vec![
+            "/opt/spark/examples/src/main/resources/people.txt".to_string()
+        ])
+        .collect()
+        .await?;
+    assert_eq!(rows.iter().map(|batch| batch.num_rows()).sum::<usize>(), 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parquet_and_orc_shortcuts_work() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    for dataframe in [
+        session.clone().read().parquet(vec![
+            "/opt/spark/examples/src/main/resources/users.parquet".to_string(),
+        ]),
+        session.clone().read().orc(vec![
+            "/opt/spark/examples/src/main/resources/users.orc".to_string()
+        ]),
+    ] {
+        let rows = dataframe.select(vec!["name"]).collect().await?;
+        assert_batches_eq!(
+            vec![
+                "+--------+",
+                "| name   |",
+                "+--------+",
+                "| Alyssa |",
+                "| Ben    |",
+                "+--------+",
+            ],
+            &rows
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_json_from_dataframe_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let payloads = session.clone().sql(
+        r#"SELECT * FROM VALUES ('{"name": "Michael", "salary": 3000}'), ('{"name": "Andy", "salary": 4500}') AS payloads(value)"#
+            .to_owned(),
+    );
+    let rows = session
+        .read()
+        .schema("name STRING, salary INT")?
+        .json_from(&payloads)
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+---------+--------+",
+            "| name    | salary |",
+            "+---------+--------+",
+            "| Michael | 3000   |",
+            "| Andy    | 4500   |",
+            "+---------+--------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_csv_from_dataframe_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let lines = session.clone().sql(
+        "SELECT * FROM VALUES ('name;age'), ('Jorge;30'), ('Bob;32') AS lines(value)".to_owned(),
+    );
+    let rows = session
+        .read()
+        .options(CsvOptions {
+            header: Some(true),
+            sep: Some(";".to_string()),
+            infer_schema: Some(true),
+            ..Default::default()
+        })
+        .csv_from(&lines)
+        .select_expr(vec!["name".to_string(), "age + 1 AS next_age".to_string()])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+-------+----------+",
+            "| name  | next_age |",
+            "+-------+----------+",
+            "| Jorge | 31       |",
+            "| Bob   | 33       |",
+            "+-------+----------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
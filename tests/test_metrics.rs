@@ -0,0 +1,100 @@
+mod common;
+mod test_util;
+use futures::TryStreamExt;
+use spark_connect_rust::column::expr;
+use spark_connect_rust::error::SparkError;
+use spark_connect_rust::metrics::Observation;
+use spark_connect_rust::spark::expression::literal::LiteralType;
+use std::error::Error;
+
+#[tokio::test]
+async fn test_collect_with_metrics_returns_operator_tree() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let result = dataframe
+        .select(vec!["name".to_string()])
+        .collect_with_metrics()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+---------+",
+            "| name    |",
+            "+---------+",
+            "| Michael |",
+            "| Andy    |",
+            "| Justin  |",
+            "| Berta   |",
+            "+---------+",
+        ],
+        &result.batches
+    );
+    assert!(!result.metrics.operators.is_empty());
+    assert_eq!(result.metrics.roots().count(), 1);
+    let rendered = result.metrics.to_string();
+    let root = result.metrics.roots().next().unwrap();
+    assert!(rendered.starts_with(&root.name));
+    assert_eq!(result.metrics.slowest(1).len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_observe_fills_observation_after_action() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let observation = Observation::new("employees".to_string());
+    assert_eq!(observation.values(), None);
+    dataframe
+        .observe(
+            &observation,
+            vec![expr("count(*) AS rows"), expr("max(salary) AS max_salary")],
+        )?
+        .collect()
+        .await?;
+    let values: Vec<_> = observation
+        .values()
+        .unwrap()
+        .into_iter()
+        .map(|literal| literal.literal_type)
+        .collect();
+    assert_eq!(
+        values,
+        vec![Some(LiteralType::Long(4)), Some(LiteralType::Long(4500))]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_observe_fills_observation_of_streamed_dataframe() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let observation = Observation::new("streamed_employees".to_string());
+    let names: Vec<(String,)> = dataframe
+        .observe(&observation, vec![expr("count(*) AS rows")])?
+        .select(vec!["name"])
+        .to_stream_as()
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(names.len(), 4);
+    let values: Vec<_> = observation
+        .values()
+        .unwrap()
+        .into_iter()
+        .map(|literal| literal.literal_type)
+        .collect();
+    assert_eq!(values, vec![Some(LiteralType::Long(4))]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_observe_rejects_pending_observation_with_same_name() -> Result<(), Box<dyn Error>> {
+    let dataframe = common::create_employees_dataframe().await?;
+    let first = Observation::new("duplicated".to_string());
+    let second = Observation::new("duplicated".to_string());
+    let observed = dataframe.observe(&first, vec![expr("count(*) AS rows")])?;
+    let result = dataframe.observe(&second, vec![expr("count(*) AS rows")]);
+    assert!(matches!(result, Err(SparkError::InvalidArgument(_))));
+
+    // Once filled, the observation is released and its name can be reused.
+    observed.collect().await?;
+    assert!(first.values().is_some());
+    dataframe.observe(&second, vec![expr("count(*) AS rows")])?;
+    Ok(())
+}
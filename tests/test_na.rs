@@ -0,0 +1,121 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::dataframe::{DataFrame, DropHow};
+use spark_connect_rust::error::SparkError;
+use spark_connect_rust::spark::expression::Literal;
+use spark_connect_rust::RemoteSparkSession;
+use std::collections::HashMap;
+use std::error::Error;
+use std::rc::Rc;
+
+type Person = (Option<String>, Option<i32>, Option<f64>, Option<bool>);
+
+fn create_people_dataframe(session: Rc<RemoteSparkSession>) -> DataFrame {
+    session.sql(
+        "SELECT * FROM VALUES \
+         ('Alice', 10, 80.5D, true), \
+         (NULL, NULL, NULL, NULL), \
+         ('Bob', NULL, 60.0D, NULL) \
+         AS people(name, age, height, member)"
+            .to_owned(),
+    )
+}
+
+fn subset(cols: &[&str]) -> Option<Vec<String>> {
+    Some(cols.iter().map(|col| col.to_string()).collect())
+}
+
+#[tokio::test]
+async fn test_fill_replaces_missing_values_of_matching_type() -> Result<(), Box<dyn Error>> {
+    let people = create_people_dataframe(new_session().await?);
+    let rows: Vec<Person> = people.na().fill(0, None).collect_as().await?;
+    assert_eq!(
+        rows,
+        vec![
+            (Some("Alice".to_string()), Some(10), Some(80.5), Some(true)),
+            (None, Some(0), Some(0.0), None),
+            (Some("Bob".to_string()), Some(0), Some(60.0), None),
+        ]
+    );
+    let rows: Vec<Person> = people
+        .na()
+        .fill("unknown", subset(&["name"]))
+        .collect_as()
+        .await?;
+    assert_eq!(rows[1], (Some("unknown".to_string()), None, None, None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fill_map_fills_each_column_with_its_value() -> Result<(), Box<dyn Error>> {
+    let people = create_people_dataframe(new_session().await?);
+    let values: HashMap<String, Literal> = vec![
+        ("name".to_string(), "unknown".into()),
+        ("age".to_string(), 18.into()),
+        ("member".to_string(), false.into()),
+    ]
+    .into_iter()
+    .collect();
+    let rows: Vec<Person> = people.na().fill_map(values).collect_as().await?;
+    assert_eq!(
+        rows[1],
+        (Some("unknown".to_string()), Some(18), None, Some(false))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drop_removes_rows_with_missing_values() -> Result<(), Box<dyn Error>> {
+    let people = create_people_dataframe(new_session().await?);
+    let names = |df: DataFrame| async move {
+        let rows: Vec<Person> = df.collect_as().await?;
+        Ok::<_, SparkError>(rows.into_iter().map(|row| row.0).collect::<Vec<_>>())
+    };
+    let alice = Some("Alice".to_string());
+    let bob = Some("Bob".to_string());
+    assert_eq!(
+        names(people.na().drop(DropHow::Any, None, None)).await?,
+        vec![alice.clone()]
+    );
+    assert_eq!(
+        names(people.na().drop(DropHow::All, None, None)).await?,
+        vec![alice.clone(), bob.clone()]
+    );
+    assert_eq!(
+        names(people.na().drop(DropHow::All, Some(3), None)).await?,
+        vec![alice.clone()]
+    );
+    assert_eq!(
+        names(people.na().drop(DropHow::Any, None, subset(&["height"]))).await?,
+        vec![alice, bob]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replace_replaces_values() -> Result<(), Box<dyn Error>> {
+    let people = create_people_dataframe(new_session().await?);
+    let rows: Vec<Person> = people
+        .na()
+        .replace(
+            vec!["Alice", "Bob"],
+            vec!["Alicia", "Robert"],
+            subset(&["name"]),
+        )?
+        .collect_as()
+        .await?;
+    assert_eq!(rows[0].0, Some("Alicia".to_string()));
+    assert_eq!(rows[2].0, Some("Robert".to_string()));
+    let rows: Vec<Person> = people
+        .na()
+        .replace(vec![10], vec![11.0], None)?
+        .collect_as()
+        .await?;
+    assert_eq!(rows[0].1, Some(11));
+    assert!(matches!(
+        people.na().replace(vec![1, 2], vec![3], None),
+        Err(SparkError::InvalidArgument(_))
+    ));
+    Ok(())
+}
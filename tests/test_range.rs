@@ -0,0 +1,31 @@
+mod common;
+mod test_util;
+use common::new_session;
+use std::collections::HashSet;
+use std::error::Error;
+
+#[tokio::test]
+async fn test_range_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows = session.range(0, 10, 3, None).collect().await?;
+    assert_batches_eq!(
+        vec!["+----+", "| id |", "+----+", "| 0  |", "| 3  |", "| 6  |", "| 9  |", "+----+",],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_range_with_num_partitions_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let partitions: HashSet<i32> = session
+        .range(0, 100, 1, Some(4))
+        .select_expr(vec!["spark_partition_id() AS partition".to_string()])
+        .collect_as::<(i32,)>()
+        .await?
+        .into_iter()
+        .map(|(partition,)| partition)
+        .collect();
+    assert_eq!(partitions, (0..4).collect());
+    Ok(())
+}
@@ -0,0 +1,67 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::column::{col, expr};
+use spark_connect_rust::dataframe::DataFrame;
+use spark_connect_rust::error::SparkError;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// Returns the ids of each partition, keyed by partition id.
+async fn ids_by_partition(dataframe: DataFrame) -> Result<HashMap<i32, Vec<i64>>, SparkError> {
+    let rows = dataframe
+        .select(vec![expr("spark_partition_id()"), col("id")])
+        .collect_as::<(i32, i64)>()
+        .await?;
+    let mut partitions: HashMap<i32, Vec<i64>> = HashMap::new();
+    for (partition, id) in rows {
+        partitions.entry(partition).or_default().push(id);
+    }
+    Ok(partitions)
+}
+
+#[tokio::test]
+async fn test_repartition_and_coalesce_work() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session.range(0, 100, 1, Some(2));
+    assert_eq!(ids_by_partition(dataframe.repartition(5)).await?.len(), 5);
+    assert_eq!(ids_by_partition(dataframe.coalesce(1)).await?.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repartition_by_groups_equal_values() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let rows = session
+        .range(0, 100, 1, Some(4))
+        .select(vec![expr("id % 10 AS key")])
+        .repartition_by(Some(3), vec![col("key")])
+        .select(vec![expr("spark_partition_id()"), col("key")])
+        .collect_as::<(i32, i64)>()
+        .await?;
+    let mut partition_of_key: HashMap<i64, HashSet<i32>> = HashMap::new();
+    for (partition, key) in rows {
+        partition_of_key.entry(key).or_default().insert(partition);
+    }
+    assert_eq!(partition_of_key.len(), 10);
+    assert!(partition_of_key
+        .values()
+        .all(|partitions| partitions.len() == 1));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repartition_by_range_orders_partitions() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let partitions = ids_by_partition(
+        session
+            .range(0, 100, 1, Some(4))
+            .repartition_by_range(Some(2), vec![col("id").desc()]),
+    )
+    .await?;
+    assert_eq!(partitions.len(), 2);
+    let first_min = partitions[&0].iter().min().unwrap();
+    let second_max = partitions[&1].iter().max().unwrap();
+    assert!(first_min > second_max);
+    Ok(())
+}
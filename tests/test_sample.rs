@@ -0,0 +1,77 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::column::expr;
+use spark_connect_rust::dataframe::DataFrame;
+use spark_connect_rust::error::SparkError;
+use std::collections::HashMap;
+use std::error::Error;
+
+async fn ids(dataframe: &DataFrame) -> Result<Vec<i64>, SparkError> {
+    let mut ids: Vec<i64> = dataframe
+        .collect_as::<(i64,)>()
+        .await?
+        .into_iter()
+        .map(|(id,)| id)
+        .collect();
+    ids.sort();
+    Ok(ids)
+}
+
+#[tokio::test]
+async fn test_sample_with_seed_is_reproducible() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session.range(0, 1000, 1, None);
+    let first = ids(&dataframe.sample(0.1, false, Some(42))).await?;
+    let second = ids(&dataframe.sample(0.1, false, Some(42))).await?;
+    assert_eq!(first, second);
+    assert!(!first.is_empty() && first.len() < 200);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_random_split_partitions_rows() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session.range(0, 1000, 1, Some(4));
+    let splits = dataframe.random_split(vec![3.0, 1.0], Some(7))?;
+    assert_eq!(splits.len(), 2);
+    let train = ids(&splits[0]).await?;
+    let test = ids(&splits[1]).await?;
+    assert!(train.len() > test.len());
+    let mut all: Vec<i64> = train.into_iter().chain(test).collect();
+    all.sort();
+    assert_eq!(all, (0..1000).collect::<Vec<i64>>());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_random_split_rejects_invalid_weights() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session.range(0, 10, 1, None);
+    for weights in [
+        vec![1.0, -1.0],
+        vec![1.0, f64::NAN],
+        vec![1.0, f64::INFINITY],
+        vec![0.0, 0.0],
+    ] {
+        let result = dataframe.random_split(weights, None);
+        assert!(matches!(result, Err(SparkError::InvalidArgument(_))));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sample_by_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let fractions: HashMap<i64, f64> = vec![(0, 1.0), (1, 0.0)].into_iter().collect();
+    let sampled = session
+        .range(0, 30, 1, None)
+        .select(vec![expr("id % 3 AS key"), expr("id")])
+        .sample_by(expr("key"), fractions, Some(1))
+        .select(vec!["id"]);
+    assert_eq!(
+        ids(&sampled).await?,
+        (0..30).step_by(3).collect::<Vec<i64>>()
+    );
+    Ok(())
+}
@@ -50,3 +50,23 @@ async fn test_collect_returns_right_values() -> Result<(), Box<dyn Error>> {
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn test_collect_empty_result_returns_schema() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let dataframe = session.sql(
+        "SELECT name, salary FROM json.`/opt/spark/examples/src/main/resources/employees.json` WHERE salary > 10000"
+            .to_owned(),
+    );
+    let rows = dataframe.collect().await?;
+    assert_batches_eq!(
+        vec![
+            "+------+--------+",
+            "| name | salary |",
+            "+------+--------+",
+            "+------+--------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
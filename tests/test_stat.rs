@@ -0,0 +1,76 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::dataframe::DataFrame;
+use spark_connect_rust::RemoteSparkSession;
+use std::error::Error;
+use std::rc::Rc;
+
+fn create_points_dataframe(session: Rc<RemoteSparkSession>) -> DataFrame {
+    session.sql(
+        "SELECT * FROM VALUES \
+         (1, 2.0D, 'a', 'u'), (2, 4.0D, 'b', 'u'), (3, 6.0D, 'a', 'v'), (4, 8.0D, 'b', 'v') \
+         AS points(x, y, key, side)"
+            .to_owned(),
+    )
+}
+
+#[tokio::test]
+async fn test_describe_and_summary_compute_statistics() -> Result<(), Box<dyn Error>> {
+    let points = create_points_dataframe(new_session().await?);
+    let rows: Vec<(String, Option<String>)> =
+        points.describe(vec!["x".to_string()]).collect_as().await?;
+    let statistics: Vec<&str> = rows.iter().map(|row| row.0.as_str()).collect();
+    assert_eq!(statistics, vec!["count", "mean", "stddev", "min", "max"]);
+    assert_eq!(rows[1].1, Some("2.5".to_string()));
+    let rows: Vec<(String, Option<String>)> = points
+        .select(vec!["x"])
+        .summary(vec!["count".to_string(), "max".to_string()])
+        .collect_as()
+        .await?;
+    assert_eq!(
+        rows,
+        vec![
+            ("count".to_string(), Some("4".to_string())),
+            ("max".to_string(), Some("4".to_string())),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_crosstab_and_freq_items_work() -> Result<(), Box<dyn Error>> {
+    let points = create_points_dataframe(new_session().await?);
+    let mut rows: Vec<(String, i64, i64)> =
+        points.stat().crosstab("key", "side").collect_as().await?;
+    rows.sort();
+    assert_eq!(rows, vec![("a".to_string(), 1, 1), ("b".to_string(), 1, 1)]);
+    let rows: Vec<(Vec<String>,)> = points
+        .stat()
+        .freq_items(vec!["key".to_string()], Some(0.5))
+        .collect_as()
+        .await?;
+    let mut items = rows[0].0.clone();
+    items.sort();
+    assert_eq!(items, vec!["a".to_string(), "b".to_string()]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_scalar_statistics_are_numbers() -> Result<(), Box<dyn Error>> {
+    let points = create_points_dataframe(new_session().await?);
+    let quantiles = points
+        .stat()
+        .approx_quantile(
+            vec!["x".to_string(), "y".to_string()],
+            vec![0.0, 0.5, 1.0],
+            0.0,
+        )
+        .await?;
+    assert_eq!(quantiles, vec![vec![1.0, 2.0, 4.0], vec![2.0, 4.0, 8.0]]);
+    let corr = points.stat().corr("x", "y").await?;
+    assert!((corr - 1.0).abs() < 1e-9);
+    let cov = points.stat().cov("x", "y").await?;
+    assert!((cov - 10.0 / 3.0).abs() < 1e-9);
+    Ok(())
+}
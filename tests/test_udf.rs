@@ -0,0 +1,99 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::column::{col, expr};
+use spark_connect_rust::spark;
+use std::error::Error;
+
+/// Jar with the UDFs under `tests/resources/udf`, compiled against the
+/// `org.apache.spark.sql.api.java` interfaces of Spark 3.5.
+const UDF_JAR: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/resources/udf/spark-connect-rust-udf.jar"
+);
+
+fn integer_type() -> spark::DataType {
+    spark::DataType {
+        kind: Some(spark::data_type::Kind::Integer(spark::data_type::Integer {
+            type_variation_reference: 0,
+        })),
+    }
+}
+
+fn boolean_type() -> spark::DataType {
+    spark::DataType {
+        kind: Some(spark::data_type::Kind::Boolean(spark::data_type::Boolean {
+            type_variation_reference: 0,
+        })),
+    }
+}
+
+#[tokio::test]
+async fn test_java_udf_in_select_and_filter_works() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    session.add_jar(UDF_JAR.to_string()).await?;
+    let string_length = session
+        .clone()
+        .udf()
+        .register_java(
+            "string_length".to_string(),
+            "org.sparkconnectrust.udf.StringLength".to_string(),
+            Some(integer_type()),
+            true,
+        )
+        .await?;
+    let longer_than = session
+        .clone()
+        .udf()
+        .register_java(
+            "longer_than".to_string(),
+            "org.sparkconnectrust.udf.LongerThan".to_string(),
+            Some(boolean_type()),
+            true,
+        )
+        .await?;
+    let rows = session
+        .sql(
+            "SELECT * FROM json.`/opt/spark/examples/src/main/resources/employees.json`".to_owned(),
+        )
+        .filter(longer_than.call(vec![col("name"), expr("4")]))
+        .select(vec![
+            col("name"),
+            string_length.call(vec![col("name")]).alias("length"),
+        ])
+        .collect()
+        .await?;
+    assert_batches_eq!(
+        vec![
+            "+---------+--------+",
+            "| name    | length |",
+            "+---------+--------+",
+            "| Michael | 7      |",
+            "| Justin  | 6      |",
+            "| Berta   | 5      |",
+            "+---------+--------+",
+        ],
+        &rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_register_java_udf_with_missing_class_fails() -> Result<(), Box<dyn Error>> {
+    let session = new_session().await?;
+    let result = session
+        .udf()
+        .register_java(
+            "to_upper".to_string(),
+            "com.example.MissingUpperUDF".to_string(),
+            Some(spark::DataType {
+                kind: Some(spark::data_type::Kind::String(spark::data_type::String {
+                    type_variation_reference: 0,
+                })),
+            }),
+            true,
+        )
+        .await;
+    assert!(result.is_err());
+    Ok(())
+}
@@ -1,9 +1,10 @@
 /// Kindly inspired to the macro from DataFusion
-/// `assert_batch_eq!(expected_lines: &[&str], batches: &[RecordBatch])`
+/// `assert_batch_eq!(expected_lines: Vec<&str>, batches: &[RecordBatch])`
 #[macro_export]
 macro_rules! assert_batches_eq {
     ($EXPECTED_LINES: expr, $CHUNKS: expr) => {
-        let expected_lines: Vec<String> = $EXPECTED_LINES.iter().map(|&s| s.into()).collect();
+        let expected_lines: Vec<&str> = $EXPECTED_LINES;
+        let expected_lines: Vec<String> = expected_lines.into_iter().map(String::from).collect();
 
         let formatted = arrow::util::pretty::pretty_format_batches($CHUNKS)
             .unwrap()
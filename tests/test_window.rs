@@ -0,0 +1,130 @@
+mod common;
+mod test_util;
+use common::new_session;
+use spark_connect_rust::column::{col, expr};
+use spark_connect_rust::dataframe::DataFrame;
+use spark_connect_rust::functions::{dense_rank, lag, lead, ntile, rank, row_number};
+use spark_connect_rust::spark::expression::Literal;
+use spark_connect_rust::window::{Boundary, Window};
+use spark_connect_rust::RemoteSparkSession;
+use std::error::Error;
+use std::rc::Rc;
+
+fn create_salaries_dataframe(session: Rc<RemoteSparkSession>) -> DataFrame {
+    session.sql(
+        "SELECT * FROM VALUES \
+         ('eng', 'a', 100), ('eng', 'b', 200), ('eng', 'c', 200), ('ops', 'd', 50), ('ops', 'e', 80) \
+         AS salaries(department, name, salary)"
+            .to_owned(),
+    )
+}
+
+#[tokio::test]
+async fn test_ranking_functions_over_window() -> Result<(), Box<dyn Error>> {
+    let salaries = create_salaries_dataframe(new_session().await?);
+    let window = Window::partition_by(vec![col("department")]).order_by(vec![col("salary").desc()]);
+    let mut rows: Vec<(String, i32, i32, i32)> = salaries
+        .select(vec![
+            col("name"),
+            row_number()
+                .over(
+                    &window
+                        .clone()
+                        .order_by(vec![col("salary").desc(), col("name")]),
+                )
+                .alias("row_number"),
+            rank().over(&window).alias("rank"),
+            dense_rank().over(&window).alias("dense_rank"),
+        ])
+        .collect_as()
+        .await?;
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            ("a".to_string(), 3, 3, 2),
+            ("b".to_string(), 1, 1, 1),
+            ("c".to_string(), 2, 1, 1),
+            ("d".to_string(), 2, 2, 2),
+            ("e".to_string(), 1, 1, 1),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_top_n_per_group() -> Result<(), Box<dyn Error>> {
+    let salaries = create_salaries_dataframe(new_session().await?);
+    let window = Window::partition_by(vec![col("department")])
+        .order_by(vec![col("salary").desc(), col("name")]);
+    let mut rows: Vec<(String,)> = salaries
+        .select(vec![col("name"), row_number().over(&window).alias("rank")])
+        .filter(expr("rank = 1"))
+        .select(vec!["name"])
+        .collect_as()
+        .await?;
+    rows.sort();
+    assert_eq!(rows, vec![("b".to_string(),), ("e".to_string(),)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_offset_functions_over_window() -> Result<(), Box<dyn Error>> {
+    let salaries = create_salaries_dataframe(new_session().await?);
+    let window =
+        Window::partition_by(vec![col("department")]).order_by(vec![col("salary"), col("name")]);
+    let mut rows: Vec<(String, Option<i32>, i32, i32)> = salaries
+        .select(vec![
+            col("name"),
+            lag(col("salary"), 1, None).over(&window).alias("previous"),
+            lead(col("salary"), 1, Some(Literal::from(0).into()))
+                .over(&window)
+                .alias("next"),
+            ntile(2).over(&window).alias("half"),
+        ])
+        .collect_as()
+        .await?;
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            ("a".to_string(), None, 200, 1),
+            ("b".to_string(), Some(100), 200, 1),
+            ("c".to_string(), Some(200), 0, 2),
+            ("d".to_string(), None, 80, 1),
+            ("e".to_string(), Some(50), 0, 2),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_window_frames() -> Result<(), Box<dyn Error>> {
+    let salaries = create_salaries_dataframe(new_session().await?);
+    let running = Window::partition_by(vec![col("department")])
+        .order_by(vec![col("salary"), col("name")])
+        .rows_between(Boundary::Unbounded, Boundary::CurrentRow);
+    let nearby = Window::partition_by(vec![col("department")])
+        .order_by(vec![col("salary")])
+        .range_between(Boundary::Offset(-100), Boundary::CurrentRow);
+    let mut rows: Vec<(String, i64, i64)> = salaries
+        .select(vec![
+            col("name"),
+            expr("sum(salary)").over(&running).alias("running"),
+            expr("sum(salary)").over(&nearby).alias("nearby"),
+        ])
+        .collect_as()
+        .await?;
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            ("a".to_string(), 100, 100),
+            ("b".to_string(), 300, 500),
+            ("c".to_string(), 500, 500),
+            ("d".to_string(), 50, 50),
+            ("e".to_string(), 130, 130),
+        ]
+    );
+    Ok(())
+}